@@ -0,0 +1,80 @@
+//! Compares filling large areas pixel by pixel using `draw_iter` with the
+//! optimized `fill_solid`, `fill_contiguous` and `clear` of the canvases.
+//!
+//! Run with `cargo bench --bench fill`.
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use embedded_canvas::{CCanvas, Canvas};
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+const ITERATIONS: u32 = 100;
+
+const SIZE: Size = Size::new(320, 240);
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    // warm up
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iteration = start.elapsed() / ITERATIONS;
+
+    println!("{name:<40} {per_iteration:>12?}");
+
+    per_iteration
+}
+
+fn bench_target<D>(name: &str, target: &mut D)
+where
+    D: DrawTarget<Color = Rgb565>,
+    D::Error: core::fmt::Debug,
+{
+    // a background larger than the canvas
+    let area = Rectangle::new(Point::new(-10, -10), SIZE + Size::new(20, 20));
+
+    let draw_iter = bench(&format!("{name} draw_iter"), || {
+        target
+            .draw_iter(area.points().map(|point| Pixel(point, Rgb565::BLUE)))
+            .unwrap();
+        black_box(&*target);
+    });
+    let fill_solid = bench(&format!("{name} fill_solid"), || {
+        target.fill_solid(&area, Rgb565::BLUE).unwrap();
+        black_box(&*target);
+    });
+    let fill_contiguous = bench(&format!("{name} fill_contiguous"), || {
+        let colors = (0..).map(|i: u32| Rgb565::new(i as u8, (i >> 5) as u8, (i >> 11) as u8));
+
+        target.fill_contiguous(&area, colors).unwrap();
+        black_box(&*target);
+    });
+    let clear = bench(&format!("{name} clear"), || {
+        target.clear(Rgb565::BLUE).unwrap();
+        black_box(&*target);
+    });
+
+    println!(
+        "{name} speedup over draw_iter: fill_solid {:.1}x, fill_contiguous {:.1}x, clear {:.1}x\n",
+        draw_iter.as_secs_f64() / fill_solid.as_secs_f64(),
+        draw_iter.as_secs_f64() / fill_contiguous.as_secs_f64(),
+        draw_iter.as_secs_f64() / clear.as_secs_f64(),
+    );
+}
+
+fn main() {
+    bench_target("Canvas", &mut Canvas::<Rgb565>::new(SIZE));
+    bench_target(
+        "CanvasAt",
+        &mut Canvas::<Rgb565>::new(SIZE).place_at(Point::new(5, 5)),
+    );
+
+    let mut ccanvas = Box::new(CCanvas::<Rgb565, 320, 240>::new());
+    bench_target("CCanvas", ccanvas.as_mut());
+    let mut ccanvas_at = Box::new(ccanvas.place_at(Point::new(5, 5)));
+    bench_target("CCanvasAt", ccanvas_at.as_mut());
+}
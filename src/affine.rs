@@ -0,0 +1,295 @@
+//! Affine transformations of canvases using fixed-point math.
+use embedded_graphics_core::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+
+/// The fractional bits of the fixed-point values of [`Affine`].
+const FRACTION_BITS: u32 = 16;
+
+/// `sin` of the angles from 0 to 90 degrees in fixed-point (see [`Affine::ONE`]).
+const SIN_TABLE: [i32; 91] = [
+    0, 1144, 2287, 3430, 4572, 5712, 6850, 7987, 9121, 10252, 11380, 12505, 13626, 14742, 15855,
+    16962, 18064, 19161, 20252, 21336, 22415, 23486, 24550, 25607, 26656, 27697, 28729, 29753,
+    30767, 31772, 32768, 33754, 34729, 35693, 36647, 37590, 38521, 39441, 40348, 41243, 42126,
+    42995, 43852, 44695, 45525, 46341, 47143, 47930, 48703, 49461, 50203, 50931, 51643, 52339,
+    53020, 53684, 54332, 54963, 55578, 56175, 56756, 57319, 57865, 58393, 58903, 59396, 59870,
+    60326, 60764, 61183, 61584, 61966, 62328, 62672, 62997, 63303, 63589, 63856, 64104, 64332,
+    64540, 64729, 64898, 65048, 65177, 65287, 65376, 65446, 65496, 65526, 65536,
+];
+
+/// An affine transformation (rotation, scaling, shearing and translation)
+/// implemented with integer fixed-point math, so it works without an FPU.
+///
+/// All the values are fixed-point numbers with 16 fractional bits,
+/// i.e. [`Affine::ONE`] is `1.0` and `Affine::ONE / 2` is `0.5`.
+///
+/// The transformation maps a point `(x, y)` to:
+///
+/// ```text
+/// x' = a * x + b * y + c
+/// y' = d * x + e * y + f
+/// ```
+///
+/// Transformations are combined with [`Affine::then`].
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::Affine;
+///
+/// // scale by 1.5 and then rotate by 30 degrees clockwise
+/// let affine = Affine::scale(Affine::ONE * 3 / 2, Affine::ONE * 3 / 2).then(Affine::rotation(30));
+/// assert!(affine.is_some());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Affine {
+    a: i32,
+    b: i32,
+    c: i32,
+    d: i32,
+    e: i32,
+    f: i32,
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Affine {
+    /// The fixed-point value of `1.0`.
+    pub const ONE: i32 = 1 << FRACTION_BITS;
+
+    /// The transformation which keeps every point in place.
+    pub const IDENTITY: Affine = Affine::new(Self::ONE, 0, 0, 0, Self::ONE, 0);
+
+    /// Create a new [`Affine`] transformation from the fixed-point values of the matrix.
+    pub const fn new(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    /// Create a translation by the given [`Point`].
+    ///
+    /// Returns [`None`] if a coordinate doesn't fit in the fixed-point values,
+    /// i.e. it's outside of `-32768..=32767`.
+    pub const fn translation(by: Point) -> Option<Self> {
+        match (by.x.checked_mul(Self::ONE), by.y.checked_mul(Self::ONE)) {
+            (Some(x), Some(y)) => Some(Self::new(Self::ONE, 0, x, 0, Self::ONE, y)),
+            _ => None,
+        }
+    }
+
+    /// Create a rotation around the origin by the given angle in degrees.
+    ///
+    /// Positive angles rotate clockwise on the display, because the y axis points down.
+    pub fn rotation(degrees: i32) -> Self {
+        let (sin, cos) = sin_cos(degrees);
+
+        Self::new(cos, -sin, 0, sin, cos, 0)
+    }
+
+    /// Create a scaling by the given fixed-point factors.
+    pub const fn scale(x: i32, y: i32) -> Self {
+        Self::new(x, 0, 0, 0, y, 0)
+    }
+
+    /// Create a shearing by the given fixed-point factors.
+    ///
+    /// The `x` factor shifts the points horizontally in proportion to their y coordinate
+    /// and the `y` factor shifts them vertically in proportion to their x coordinate.
+    pub const fn shear(x: i32, y: i32) -> Self {
+        Self::new(Self::ONE, x, 0, y, Self::ONE, 0)
+    }
+
+    /// Returns the transformation which applies `self` and then `next`.
+    ///
+    /// Returns [`None`] if a value of the combined transformation doesn't fit
+    /// in the fixed-point values, e.g. when scaling twice by a large factor.
+    pub fn then(self, next: Affine) -> Option<Self> {
+        let mul = |lhs: i32, rhs: i32| (i64::from(lhs) * i64::from(rhs)) >> FRACTION_BITS;
+
+        let values = [
+            mul(next.a, self.a) + mul(next.b, self.d),
+            mul(next.a, self.b) + mul(next.b, self.e),
+            mul(next.a, self.c) + mul(next.b, self.f) + i64::from(next.c),
+            mul(next.d, self.a) + mul(next.e, self.d),
+            mul(next.d, self.b) + mul(next.e, self.e),
+            mul(next.d, self.c) + mul(next.e, self.f) + i64::from(next.f),
+        ]
+        .map(i32::try_from);
+        match values {
+            [Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f)] => Some(Self::new(a, b, c, d, e, f)),
+            _ => None,
+        }
+    }
+
+    /// Returns the inverse transformation.
+    ///
+    /// Returns [`None`] if the transformation can't be inverted,
+    /// e.g. a scaling by `0`.
+    pub fn inverse(&self) -> Option<Self> {
+        let (a, b, c) = (i128::from(self.a), i128::from(self.b), i128::from(self.c));
+        let (d, e, f) = (i128::from(self.d), i128::from(self.e), i128::from(self.f));
+
+        // the determinant has 32 fractional bits
+        let determinant = a * e - b * d;
+        if determinant == 0 {
+            return None;
+        }
+
+        let div = |value: i128| (value << (2 * FRACTION_BITS)) / determinant;
+        let (inv_a, inv_b, inv_d, inv_e) = (div(e), div(-b), div(-d), div(a));
+        let inv_c = -(inv_a * c + inv_b * f) >> FRACTION_BITS;
+        let inv_f = -(inv_d * c + inv_e * f) >> FRACTION_BITS;
+
+        let values = [inv_a, inv_b, inv_c, inv_d, inv_e, inv_f].map(i32::try_from);
+        match values {
+            [Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f)] => Some(Self::new(a, b, c, d, e, f)),
+            _ => None,
+        }
+    }
+
+    /// Returns the transformed [`Point`].
+    ///
+    /// The result is rounded down to whole pixels.
+    pub fn transform_point(&self, point: Point) -> Point {
+        let (x, y) = self.transform_fixed(
+            i64::from(point.x) << FRACTION_BITS,
+            i64::from(point.y) << FRACTION_BITS,
+        );
+
+        Point::new((x >> FRACTION_BITS) as i32, (y >> FRACTION_BITS) as i32)
+    }
+
+    /// Returns the bounding box of a canvas with the given `size`
+    /// after the transformation.
+    ///
+    /// Returns [`None`] if the bounding box doesn't fit in the [`Point`] coordinates.
+    pub fn bounding_box(&self, size: Size) -> Option<Rectangle> {
+        let (width, height) = (
+            i128::from(size.width) << FRACTION_BITS,
+            i128::from(size.height) << FRACTION_BITS,
+        );
+        // the corners of large sizes overflow `i64` when multiplied by the values
+        let (a, b, c) = (i128::from(self.a), i128::from(self.b), i128::from(self.c));
+        let (d, e, f) = (i128::from(self.d), i128::from(self.e), i128::from(self.f));
+        let corners = [(0, 0), (width, 0), (0, height), (width, height)].map(|(x, y)| {
+            (
+                ((a * x + b * y) >> FRACTION_BITS) + c,
+                ((d * x + e * y) >> FRACTION_BITS) + f,
+            )
+        });
+
+        let (mut min, mut max) = (corners[0], corners[0]);
+        for (x, y) in corners {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+
+        // round down the top left and round up the bottom right corner
+        let floor = |value: i128| i32::try_from(value >> FRACTION_BITS).ok();
+        let ceil = |value: i128| floor(value + i128::from(Self::ONE) - 1);
+        let top_left = Point::new(floor(min.0)?, floor(min.1)?);
+        let bottom_right = Point::new(ceil(max.0)?, ceil(max.1)?);
+
+        // the size is in `0..=i32::MAX`, so the bottom right corner of the rectangle fits too
+        let length = |start: i32, end: i32| u32::try_from(end.checked_sub(start)?).ok();
+        let size = Size::new(
+            length(top_left.x, bottom_right.x)?,
+            length(top_left.y, bottom_right.y)?,
+        );
+
+        Some(Rectangle::new(top_left, size))
+    }
+
+    fn transform_fixed(&self, x: i64, y: i64) -> (i64, i64) {
+        let transformed_x =
+            ((i64::from(self.a) * x + i64::from(self.b) * y) >> FRACTION_BITS) + i64::from(self.c);
+        let transformed_y =
+            ((i64::from(self.d) * x + i64::from(self.e) * y) >> FRACTION_BITS) + i64::from(self.f);
+
+        (transformed_x, transformed_y)
+    }
+}
+
+/// Returns the fixed-point `sin` and `cos` of the angle in degrees.
+fn sin_cos(degrees: i32) -> (i32, i32) {
+    let degrees = degrees.rem_euclid(360) as usize;
+    let (quadrant, angle) = (degrees / 90, degrees % 90);
+    let (sin, cos) = (SIN_TABLE[angle], SIN_TABLE[90 - angle]);
+
+    match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+/// Returns the [`Point`] in the source for the pixel at `point` in the transformed canvas,
+/// using the `inverse` transformation and sampling the center of the pixel.
+pub(crate) fn source_point(inverse: &Affine, point: Point) -> Point {
+    let half = i64::from(Affine::ONE) / 2;
+    let (x, y) = inverse.transform_fixed(
+        (i64::from(point.x) << FRACTION_BITS) + half,
+        (i64::from(point.y) << FRACTION_BITS) + half,
+    );
+
+    Point::new((x >> FRACTION_BITS) as i32, (y >> FRACTION_BITS) as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_affine() {
+        let rotation = Affine::rotation(90);
+        assert_eq!(Point::new(0, 1), rotation.transform_point(Point::new(1, 0)));
+        assert_eq!(
+            Point::new(-1, 0),
+            rotation.transform_point(Point::new(0, 1))
+        );
+        assert_eq!(Affine::IDENTITY, Affine::rotation(360));
+
+        let affine = Affine::scale(Affine::ONE * 2, Affine::ONE / 2)
+            .then(Affine::translation(Point::new(3, -1)).unwrap())
+            .unwrap();
+        assert_eq!(Point::new(7, 1), affine.transform_point(Point::new(2, 4)));
+
+        let inverse = affine.inverse().unwrap();
+        assert_eq!(Point::new(2, 4), inverse.transform_point(Point::new(7, 1)));
+        assert_eq!(Some(Affine::IDENTITY), affine.then(inverse));
+
+        // 200 * 200 doesn't fit in the fixed-point values
+        let scale = Affine::scale(Affine::ONE * 200, Affine::ONE * 200);
+        assert_eq!(None, scale.then(scale));
+        assert_eq!(None, Affine::scale(0, Affine::ONE).inverse());
+
+        assert!(Affine::translation(Point::new(-32768, 32767)).is_some());
+        assert_eq!(None, Affine::translation(Point::new(32768, 0)));
+
+        assert_eq!(
+            Some(Rectangle::new(Point::new(-4, 0), Size::new(4, 2))),
+            Affine::rotation(90).bounding_box(Size::new(2, 4))
+        );
+        // rotated by 45 degrees: sqrt(2) * 2 = 2.83
+        assert_eq!(
+            Some(Rectangle::new(Point::new(-2, 0), Size::new(4, 3))),
+            Affine::rotation(45).bounding_box(Size::new(2, 2))
+        );
+        assert_eq!(
+            None,
+            Affine::scale(Affine::ONE * 2, Affine::ONE).bounding_box(Size::new(u32::MAX, 1))
+        );
+
+        let shear = Affine::shear(Affine::ONE, 0);
+        assert_eq!(Point::new(3, 2), shear.transform_point(Point::new(1, 2)));
+        assert_eq!(
+            Point::new(0, 0),
+            source_point(&shear.inverse().unwrap(), Point::new(0, 0))
+        );
+    }
+}
@@ -0,0 +1,529 @@
+//! `Canvas`es specialised for [`BinaryColor`] which pack the color and the
+//! transparency of each pixel in 2 bits using 2 bitplanes.
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
+use embedded_graphics_core::{
+    pixelcolor::BinaryColor,
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+
+#[cfg(feature = "alloc")]
+use crate::storage::NewPixelStorage;
+use crate::{
+    canvas::{GenericCanvas, GenericCanvasAt},
+    storage::PixelStorage,
+    utils::center_offset,
+};
+
+/// Returns the page and the bit of the pixel in the column byte for the `y` coordinate.
+fn page_bit(y: usize) -> (usize, u8) {
+    (y / 8, 1 << (y % 8))
+}
+
+/// Returns the color of the pixel from the column bytes of the 2 bitplanes.
+fn bitplanes_pixel(color: u8, mask: u8, bit: u8) -> Option<BinaryColor> {
+    if mask & bit == 0 {
+        return None;
+    }
+
+    Some(BinaryColor::from(color & bit != 0))
+}
+
+/// Sets the color of the pixel in the column bytes of the 2 bitplanes,
+/// [`None`] clears the pixel in both bitplanes.
+fn set_bitplanes_pixel(color: &mut u8, mask: &mut u8, bit: u8, pixel_color: Option<BinaryColor>) {
+    match pixel_color {
+        Some(pixel_color) => {
+            *mask |= bit;
+
+            if pixel_color.is_on() {
+                *color |= bit;
+            } else {
+                *color &= !bit;
+            }
+        }
+        None => {
+            *mask &= !bit;
+            *color &= !bit;
+        }
+    }
+}
+
+/// Returns the bytes of the color and the mask bitplanes with all the pixels set to `color`.
+fn bitplanes_bytes(color: Option<BinaryColor>) -> (u8, u8) {
+    match color {
+        Some(BinaryColor::On) => (u8::MAX, u8::MAX),
+        Some(BinaryColor::Off) => (0, u8::MAX),
+        None => (0, 0),
+    }
+}
+
+/// Returns the number of pages for the given height.
+#[cfg(feature = "alloc")]
+fn pages_count(height: u32) -> usize {
+    let height = height as usize;
+
+    height / 8 + usize::from(height % 8 != 0)
+}
+
+/// A [`PixelStorage`] for [`BinaryColor`] where each pixel takes 2 bits - one in the
+/// [`Bitplanes::color`] bitplane and one in the [`Bitplanes::mask`] bitplane for the
+/// drawn pixels, using the page layout of monochrome display controllers (see [`CBitplanes`]).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct Bitplanes {
+    size: Size,
+    color: Box<[u8]>,
+    mask: Box<[u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Bitplanes {
+    /// Returns the colors bitplane, pixels which are not drawn are `0`.
+    pub fn color(&self) -> &[u8] {
+        &self.color
+    }
+
+    /// Returns the bitplane of the drawn pixels.
+    pub fn mask(&self) -> &[u8] {
+        &self.mask
+    }
+
+    /// Returns the pages of the colors bitplane, each page being `width` bytes.
+    ///
+    /// Pixels which are not drawn are `0`.
+    pub fn pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.color.chunks(self.size.width.max(1) as usize)
+    }
+
+    /// Returns the pages of the drawn pixels bitplane, each page being `width` bytes.
+    pub fn mask_pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.mask.chunks(self.size.width.max(1) as usize)
+    }
+
+    /// Returns the index of the column byte in the bitplanes and the bit of the pixel.
+    fn index(&self, point: Point) -> (usize, u8) {
+        let (page, bit) = page_bit(point.y as usize);
+
+        (page * self.size.width as usize + point.x as usize, bit)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PixelStorage for Bitplanes {
+    type Color = BinaryColor;
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn get(&self, point: Point) -> Option<BinaryColor> {
+        let (index, bit) = self.index(point);
+
+        bitplanes_pixel(self.color[index], self.mask[index], bit)
+    }
+
+    fn set(&mut self, point: Point, color: Option<BinaryColor>) {
+        let (index, bit) = self.index(point);
+
+        set_bitplanes_pixel(&mut self.color[index], &mut self.mask[index], bit, color);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NewPixelStorage for Bitplanes {
+    /// Create a new [`Bitplanes`] storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics when width * pages > [`usize::MAX`].
+    fn with_size(size: Size, color: Option<BinaryColor>) -> Self {
+        let len = size.width as usize * pages_count(size.height);
+        let (color, mask) = bitplanes_bytes(color);
+        let mut bitplanes = Self {
+            size,
+            color: vec![color; len].into_boxed_slice(),
+            mask: vec![mask; len].into_boxed_slice(),
+        };
+
+        // the rows of the last page past the height are never set
+        let rows = size.height % 8;
+        if rows != 0 {
+            let last_page = len - size.width as usize;
+            let bits = (1 << rows) - 1;
+
+            for byte in bitplanes.color[last_page..].iter_mut() {
+                *byte &= bits;
+            }
+            for byte in bitplanes.mask[last_page..].iter_mut() {
+                *byte &= bits;
+            }
+        }
+
+        bitplanes
+    }
+}
+
+/// Canvas for [`BinaryColor`] on which you can draw but it's not drawable on the display yet.
+///
+/// Each pixel takes 2 bits (see [`Bitplanes`]).
+///
+/// Draw on the [`BinaryCanvas`] using origin of [`Point::zero()`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type BinaryCanvas = GenericCanvas<Bitplanes>;
+
+#[cfg(feature = "alloc")]
+impl BinaryCanvas {
+    /// Returns the pages of the colors bitplane, each page being `width` bytes.
+    ///
+    /// Pixels which are not drawn are `0`.
+    pub fn pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.storage().pages()
+    }
+
+    /// Returns the pages of the drawn pixels bitplane, each page being `width` bytes.
+    pub fn mask_pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.storage().mask_pages()
+    }
+}
+
+/// Canvas for [`BinaryColor`] which is drawable at the provided [`Point`] (location) on the display.
+///
+/// Each pixel takes 2 bits (see [`Bitplanes`]).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type BinaryCanvasAt = GenericCanvasAt<Bitplanes>;
+
+#[cfg(feature = "alloc")]
+impl BinaryCanvasAt {
+    /// Returns the pages of the colors bitplane, each page being `width` bytes.
+    ///
+    /// Pixels which are not drawn are `0`.
+    pub fn pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.storage().pages()
+    }
+
+    /// Returns the pages of the drawn pixels bitplane, each page being `width` bytes.
+    pub fn mask_pages(&self) -> impl Iterator<Item = &[u8]> {
+        self.storage().mask_pages()
+    }
+}
+
+/// A [`PixelStorage`] for [`BinaryColor`] implemented using [const generics][const_generics_rfc].
+///
+/// Each pixel takes 2 bits - one in the [`CBitplanes::color`] bitplane
+/// and one in the [`CBitplanes::mask`] bitplane for the drawn pixels.
+///
+/// The bitplanes use the page layout of monochrome display controllers
+/// like the SSD1306 and SH1106: each byte is a column of 8 vertical pixels
+/// in a page (8 rows), with the least significant bit being the top pixel.
+/// A page is `width` bytes long and the pages follow each other from top to bottom.
+///
+/// The height is set in pages of 8 rows (`PAGES`).
+///
+/// [const_generics_rfc]: https://rust-lang.github.io/rfcs/2000-const-generics.html
+#[derive(Debug, Clone, Copy)]
+pub struct CBitplanes<const W: usize, const PAGES: usize> {
+    color: [[u8; W]; PAGES],
+    mask: [[u8; W]; PAGES],
+}
+
+impl<const W: usize, const PAGES: usize> Default for CBitplanes<W, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const PAGES: usize> CBitplanes<W, PAGES> {
+    /// Create a new [`CBitplanes`] storage without drawn pixels.
+    pub const fn new() -> Self {
+        Self {
+            color: [[0; W]; PAGES],
+            mask: [[0; W]; PAGES],
+        }
+    }
+
+    /// Create a new [`CBitplanes`] storage with all the pixels set to `color`.
+    fn with_color(color: BinaryColor) -> Self {
+        let (color, mask) = bitplanes_bytes(Some(color));
+
+        Self {
+            color: [[color; W]; PAGES],
+            mask: [[mask; W]; PAGES],
+        }
+    }
+
+    /// Returns the colors bitplane, pixels which are not drawn are `0`.
+    pub fn color(&self) -> &[[u8; W]; PAGES] {
+        &self.color
+    }
+
+    /// Returns the bitplane of the drawn pixels.
+    pub fn mask(&self) -> &[[u8; W]; PAGES] {
+        &self.mask
+    }
+}
+
+impl<const W: usize, const PAGES: usize> PixelStorage for CBitplanes<W, PAGES> {
+    type Color = BinaryColor;
+
+    fn size(&self) -> Size {
+        Size::new(W as u32, (PAGES * 8) as u32)
+    }
+
+    fn get(&self, point: Point) -> Option<BinaryColor> {
+        let (page, bit) = page_bit(point.y as usize);
+        let x = point.x as usize;
+
+        bitplanes_pixel(self.color[page][x], self.mask[page][x], bit)
+    }
+
+    fn set(&mut self, point: Point, color: Option<BinaryColor>) {
+        let (page, bit) = page_bit(point.y as usize);
+        let x = point.x as usize;
+
+        set_bitplanes_pixel(
+            &mut self.color[page][x],
+            &mut self.mask[page][x],
+            bit,
+            color,
+        );
+    }
+}
+
+/// Canvas for [`BinaryColor`] on which you can draw but it's not drawable on the display yet.
+/// Implemented using [const generics][const_generics_rfc].
+///
+/// Each pixel takes 2 bits (see [`CBitplanes`]).
+///
+/// The height of the canvas is set in pages of 8 rows (`PAGES`),
+/// e.g. a 128x64 SSD1306 display is `CBinaryCanvas<128, 8>`.
+///
+/// It can be rotated by 180 degrees ([`GenericCanvas::rotate_180`]) and flipped,
+/// but not by 90 or 270 degrees because the rotated height wouldn't be in whole pages,
+/// use a [`BinaryCanvas`] for those rotations.
+///
+/// Draw on the [`CBinaryCanvas`] using origin of [`Point::zero()`].
+///
+/// [const_generics_rfc]: https://rust-lang.github.io/rfcs/2000-const-generics.html
+pub type CBinaryCanvas<const W: usize, const PAGES: usize> = GenericCanvas<CBitplanes<W, PAGES>>;
+
+impl<const W: usize, const PAGES: usize> Default for CBinaryCanvas<W, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const PAGES: usize> CBinaryCanvas<W, PAGES> {
+    /// Create a new blank [`CBinaryCanvas`].
+    pub const fn new() -> Self {
+        GenericCanvas::from_storage(CBitplanes::new())
+    }
+
+    /// Create a [`CBinaryCanvas`] filled with a default color.
+    pub fn with_default_color(default_color: BinaryColor) -> Self {
+        GenericCanvas::from_storage(CBitplanes::with_color(default_color))
+    }
+
+    /// Create a new cropped [`CBinaryCanvas`].
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the [`CBinaryCanvas`] itself.
+    ///
+    /// If the width or height of the [`Rectangle`] is `0`, this method will
+    /// return [`None`] (see [`Rectangle::bottom_right()`])
+    pub fn crop<const NW: usize, const NPAGES: usize>(
+        &self,
+        area: &Rectangle,
+    ) -> Option<CBinaryCanvas<NW, NPAGES>> {
+        // returns None when width or height is `0`
+        area.bottom_right()?;
+
+        let mut new = CBinaryCanvas::<NW, NPAGES>::new();
+        self.crop_into(area, &mut new).ok().map(|_| new)
+    }
+}
+
+/// Canvas for [`BinaryColor`] which is drawable at the provided [`Point`] (location) on the display.
+///
+/// Each pixel takes 2 bits (see [`CBitplanes`]).
+///
+/// Same as [`CBinaryCanvas`] it can be rotated by 180 degrees and flipped,
+/// but not by 90 or 270 degrees.
+pub type CBinaryCanvasAt<const W: usize, const PAGES: usize> =
+    GenericCanvasAt<CBitplanes<W, PAGES>>;
+
+impl<const W: usize, const PAGES: usize> CBinaryCanvasAt<W, PAGES> {
+    /// Create a new blank [`CBinaryCanvasAt`].
+    pub const fn new(top_left: Point) -> Self {
+        GenericCanvasAt::from_storage(top_left, CBitplanes::new())
+    }
+
+    /// Create a [`CBinaryCanvasAt`] filled with a default color.
+    pub fn with_default_color(top_left: Point, default_color: BinaryColor) -> Self {
+        GenericCanvasAt::from_storage(top_left, CBitplanes::with_color(default_color))
+    }
+
+    /// Create a new blank [`CBinaryCanvasAt`] with a set center on the display.
+    pub fn with_center(center: Point) -> Self {
+        let top_left = center - center_offset(Size::new(W as u32, (PAGES * 8) as u32));
+
+        Self::new(top_left)
+    }
+
+    /// Create a new cropped [`CBinaryCanvasAt`].
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the **display**.
+    ///
+    /// If the width or height of the [`Rectangle`] is `0`, this method will
+    /// return [`None`] (see [`Rectangle::bottom_right()`])
+    pub fn crop<const NW: usize, const NPAGES: usize>(
+        &self,
+        area: &Rectangle,
+    ) -> Option<CBinaryCanvasAt<NW, NPAGES>> {
+        // returns None when width or height is `0`
+        area.bottom_right()?;
+
+        let mut new = CBinaryCanvasAt::<NW, NPAGES>::new(area.top_left);
+        self.crop_into(area, &mut new).ok().map(|_| new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_cbinary_canvas_pages() {
+        let mut canvas = CBinaryCanvas::<4, 2>::new();
+        assert_eq!(Size::new(4, 16), canvas.size());
+
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(0, 7), BinaryColor::On),
+                Pixel(Point::new(3, 9), BinaryColor::On),
+                Pixel(Point::new(2, 1), BinaryColor::Off),
+                // outside of the canvas
+                Pixel(Point::new(4, 0), BinaryColor::On),
+                Pixel(Point::new(0, 16), BinaryColor::On),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            [[0b1000_0001, 0, 0, 0], [0, 0, 0, 0b10]],
+            *canvas.storage().color()
+        );
+        assert_eq!(
+            [[0b1000_0001, 0, 0b10, 0], [0, 0, 0, 0b10]],
+            *canvas.storage().mask()
+        );
+
+        assert_eq!(Some(BinaryColor::Off), canvas.get_pixel(Point::new(2, 1)));
+        assert_eq!(None, canvas.get_pixel(Point::new(2, 2)));
+
+        let canvas_at = canvas.place_at(Point::new(10, 10));
+        let cropped = canvas_at
+            .crop::<2, 2>(&Rectangle::new(Point::new(12, 11), Size::new(2, 9)))
+            .expect("Should crop");
+        assert_eq!(
+            Some(BinaryColor::Off),
+            cropped.get_pixel(Point::new(12, 11))
+        );
+        assert_eq!(Some(BinaryColor::On), cropped.get_pixel(Point::new(13, 19)));
+        assert_eq!([[0, 0], [0, 1]], *cropped.storage().color());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_binary_canvas_pages() {
+        let mut canvas = BinaryCanvas::new(Size::new(3, 10));
+        assert_eq!(6, canvas.storage().color().len());
+
+        canvas
+            .draw_iter([
+                Pixel(Point::new(1, 0), BinaryColor::On),
+                Pixel(Point::new(2, 9), BinaryColor::On),
+                Pixel(Point::new(0, 8), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let mut pages = canvas.pages();
+        assert_eq!(Some(&[0, 1, 0][..]), pages.next());
+        assert_eq!(Some(&[0, 0, 0b10][..]), pages.next());
+        assert_eq!(None, pages.next());
+
+        let mut mask_pages = canvas.mask_pages();
+        assert_eq!(Some(&[0, 1, 0][..]), mask_pages.next());
+        assert_eq!(Some(&[1, 0, 0b10][..]), mask_pages.next());
+
+        let placed = canvas.place_center(Point::new(10, 10));
+        assert_eq!(Point::new(9, 6), placed.top_left);
+        assert_eq!(Some(BinaryColor::Off), placed.get_pixel(Point::new(9, 14)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_binary_canvas_default_color_pages() {
+        // the rows 3 to 7 of the page are outside of the canvas
+        let canvas = BinaryCanvas::with_default_color(Size::new(2, 3), BinaryColor::On);
+        assert_eq!(
+            [&[0b111, 0b111][..]],
+            canvas.pages().collect::<alloc::vec::Vec<_>>()[..]
+        );
+        assert_eq!(
+            [&[0b111, 0b111][..]],
+            canvas.mask_pages().collect::<alloc::vec::Vec<_>>()[..]
+        );
+
+        let canvas = BinaryCanvas::with_default_color(Size::new(1, 10), BinaryColor::Off);
+        assert_eq!(&[0xff, 0b11], canvas.storage().mask());
+        assert_eq!(Some(BinaryColor::Off), canvas.get_pixel(Point::new(0, 9)));
+    }
+
+    #[test]
+    fn test_cbinary_canvas_rotate_180() {
+        let mut canvas = CBinaryCanvas::<3, 1>::new();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 2), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let rotated = canvas.rotate_180();
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(2, 7)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(1, 5)));
+        assert_eq!(None, rotated.get_pixel(Point::new(0, 0)));
+        assert_eq!([[0, 0, 0b1000_0000]], *rotated.storage().color());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_binary_canvas_rotate() {
+        let mut canvas = BinaryCanvas::new(Size::new(3, 10));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 9), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let rotated = canvas.rotate_90();
+        assert_eq!(Size::new(10, 3), rotated.size());
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(9, 0)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(0, 2)));
+        assert_eq!(1, rotated.pages().count());
+
+        let rotated_back = rotated.rotate_270();
+        assert_eq!(canvas.storage().color(), rotated_back.storage().color());
+        assert_eq!(canvas.storage().mask(), rotated_back.storage().mask());
+    }
+}
@@ -0,0 +1,377 @@
+//! Alpha blending and blend modes of canvas pixels over the pixels already drawn on a target.
+use embedded_graphics_core::{
+    image::GetPixel,
+    pixelcolor::{
+        Bgr555, Bgr565, Bgr666, Bgr888, BinaryColor, Gray2, Gray4, Gray8, GrayColor, PixelColor,
+        Rgb555, Rgb565, Rgb666, Rgb888, RgbColor,
+    },
+    prelude::{DrawTarget, Pixel, Point},
+};
+
+/// A color with an alpha (coverage) value.
+///
+/// Draw with [`Alpha`] colors on a canvas (e.g. `CanvasAt<Alpha<Rgb565>>`)
+/// and then composite the canvas over the pixels already drawn on the display
+/// using the `draw_blended` or `draw_blended_over` methods of the canvas.
+///
+/// An `alpha` of `0` is fully transparent and [`u8::MAX`] is fully opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Alpha<C> {
+    /// The color of the pixel.
+    pub color: C,
+    /// The alpha (coverage) of the pixel.
+    pub alpha: u8,
+}
+
+impl<C> Alpha<C> {
+    /// Create a new color with the given alpha.
+    pub const fn new(color: C, alpha: u8) -> Self {
+        Self { color, alpha }
+    }
+
+    /// Create a new fully opaque color.
+    pub const fn opaque(color: C) -> Self {
+        Self::new(color, u8::MAX)
+    }
+}
+
+impl<C: AlphaBlend> Alpha<C> {
+    /// Composites the color over the `background` color.
+    pub fn over(self, background: C) -> C {
+        match self.alpha {
+            0 => background,
+            u8::MAX => self.color,
+            alpha => self.color.alpha_blend(background, alpha),
+        }
+    }
+}
+
+impl<C: PixelColor> PixelColor for Alpha<C> {
+    type Raw = ();
+}
+
+/// Colors which can be composited over a background color.
+pub trait AlphaBlend: PixelColor {
+    /// Composites `self` over the `background` color with the given `alpha` (coverage).
+    ///
+    /// An `alpha` of `0` returns the `background` and [`u8::MAX`] returns `self`.
+    fn alpha_blend(self, background: Self, alpha: u8) -> Self;
+}
+
+/// Linear interpolation between the foreground and background channel values.
+fn mix(foreground: u8, background: u8, alpha: u8) -> u8 {
+    let alpha = u32::from(alpha);
+    let mixed = u32::from(foreground) * alpha + u32::from(background) * (255 - alpha);
+
+    // rounded division by `u8::MAX`
+    ((mixed + 127) / 255) as u8
+}
+
+macro_rules! impl_alpha_blend_rgb {
+    ($($color:ty),+) => {
+        $(
+            impl AlphaBlend for $color {
+                fn alpha_blend(self, background: Self, alpha: u8) -> Self {
+                    Self::new(
+                        mix(self.r(), background.r(), alpha),
+                        mix(self.g(), background.g(), alpha),
+                        mix(self.b(), background.b(), alpha),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_alpha_blend_gray {
+    ($($color:ty),+) => {
+        $(
+            impl AlphaBlend for $color {
+                fn alpha_blend(self, background: Self, alpha: u8) -> Self {
+                    Self::new(mix(self.luma(), background.luma(), alpha))
+                }
+            }
+        )+
+    };
+}
+
+impl_alpha_blend_rgb!(Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888);
+impl_alpha_blend_gray!(Gray2, Gray4, Gray8);
+
+impl AlphaBlend for BinaryColor {
+    /// [`BinaryColor`] has no intermediate values, so the color with more
+    /// coverage wins.
+    fn alpha_blend(self, background: Self, alpha: u8) -> Self {
+        if alpha >= 128 {
+            self
+        } else {
+            background
+        }
+    }
+}
+
+/// The blend mode used for compositing a color (the foreground)
+/// over the color already drawn on the target (the background).
+///
+/// The modes are applied to each channel separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// The foreground replaces the background.
+    #[default]
+    Normal,
+    /// Multiplies the channels, the result is always darker.
+    Multiply,
+    /// Inverse of multiplying the inverted channels, the result is always lighter.
+    Screen,
+    /// Adds the channels, saturating at the maximum value.
+    Add,
+    /// Subtracts the foreground from the background, saturating at `0`.
+    Subtract,
+    /// Bitwise XOR of the channels, e.g. for inverting a [`BinaryColor`] cursor.
+    Xor,
+    /// Takes the darker (minimum) channel.
+    Darken,
+    /// Takes the lighter (maximum) channel.
+    Lighten,
+}
+
+/// Colors which can be composited over a background color using a [`BlendMode`].
+pub trait Blend: PixelColor {
+    /// Composites `self` over the `background` color using the blend `mode`.
+    fn blend(self, background: Self, mode: BlendMode) -> Self;
+}
+
+/// Blends a foreground and background channel value with the given maximum value.
+fn blend_channel(foreground: u8, background: u8, max: u8, mode: BlendMode) -> u8 {
+    let (fg, bg, max) = (u32::from(foreground), u32::from(background), u32::from(max));
+
+    let blended = match mode {
+        BlendMode::Normal => fg,
+        // rounded division by `max`
+        BlendMode::Multiply => (fg * bg + max / 2) / max,
+        BlendMode::Screen => max - ((max - fg) * (max - bg) + max / 2) / max,
+        BlendMode::Add => (fg + bg).min(max),
+        BlendMode::Subtract => bg.saturating_sub(fg),
+        BlendMode::Xor => fg ^ bg,
+        BlendMode::Darken => fg.min(bg),
+        BlendMode::Lighten => fg.max(bg),
+    };
+
+    blended as u8
+}
+
+macro_rules! impl_blend_rgb {
+    ($($color:ty),+) => {
+        $(
+            impl Blend for $color {
+                fn blend(self, background: Self, mode: BlendMode) -> Self {
+                    Self::new(
+                        blend_channel(self.r(), background.r(), Self::MAX_R, mode),
+                        blend_channel(self.g(), background.g(), Self::MAX_G, mode),
+                        blend_channel(self.b(), background.b(), Self::MAX_B, mode),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_blend_gray {
+    ($($color:ty),+) => {
+        $(
+            impl Blend for $color {
+                fn blend(self, background: Self, mode: BlendMode) -> Self {
+                    let max = Self::WHITE.luma();
+
+                    Self::new(blend_channel(self.luma(), background.luma(), max, mode))
+                }
+            }
+        )+
+    };
+}
+
+impl_blend_rgb!(Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888);
+impl_blend_gray!(Gray2, Gray4, Gray8);
+
+impl Blend for BinaryColor {
+    /// [`BinaryColor::On`] is treated as the maximum value of a single channel,
+    /// e.g. [`BlendMode::Multiply`] is a logical AND and [`BlendMode::Xor`] inverts
+    /// the background where the foreground is [`BinaryColor::On`].
+    fn blend(self, background: Self, mode: BlendMode) -> Self {
+        let channel = |color: BinaryColor| u8::from(color.is_on());
+        let blended = blend_channel(channel(self), channel(background), 1, mode);
+
+        BinaryColor::from(blended == 1)
+    }
+}
+
+/// The number of blended pixels buffered before drawing them on the target.
+const BLEND_CHUNK: usize = 64;
+
+/// Blends the pixels over the pixels read back from the target.
+pub(crate) fn draw_blended<C, D, I>(pixels: I, target: &mut D) -> Result<(), D::Error>
+where
+    C: AlphaBlend,
+    D: DrawTarget<Color = C> + GetPixel<Color = C>,
+    I: IntoIterator<Item = Pixel<Alpha<C>>>,
+{
+    let pixels = pixels
+        .into_iter()
+        .filter(|Pixel(_, color)| color.alpha != 0);
+
+    draw_composited(
+        pixels,
+        target,
+        |color: Alpha<C>, background| match background {
+            Some(background) => color.over(background),
+            None => color.color,
+        },
+    )
+}
+
+/// Composites the pixels over the pixels read back from the target using the blend `mode`.
+pub(crate) fn draw_with_blend<C, D, I>(
+    pixels: I,
+    target: &mut D,
+    mode: BlendMode,
+) -> Result<(), D::Error>
+where
+    C: Blend,
+    D: DrawTarget<Color = C> + GetPixel<Color = C>,
+    I: IntoIterator<Item = Pixel<C>>,
+{
+    draw_composited(pixels, target, |color: C, background| match background {
+        Some(background) => color.blend(background, mode),
+        None => color,
+    })
+}
+
+/// Composites the pixels over the pixels read back from the target.
+///
+/// Pixels over undrawn pixels of the target are composited over [`None`]
+/// and pixels outside of the target are skipped.
+///
+/// The target can't be read while it's being drawn on, so the composited pixels
+/// are drawn in chunks of [`BLEND_CHUNK`] pixels without allocating.
+fn draw_composited<T, C, D, I, F>(pixels: I, target: &mut D, composite: F) -> Result<(), D::Error>
+where
+    T: PixelColor,
+    C: PixelColor,
+    D: DrawTarget<Color = C> + GetPixel<Color = C>,
+    I: IntoIterator<Item = Pixel<T>>,
+    F: Fn(T, Option<C>) -> C,
+{
+    let bounds = target.bounding_box();
+    let mut pixels = pixels.into_iter();
+
+    loop {
+        let mut chunk = [None; BLEND_CHUNK];
+        let mut len = 0;
+
+        for Pixel(point, color) in pixels.by_ref() {
+            // skip pixels outside of the target
+            if bounds.contains(point) {
+                chunk[len] = Some(Pixel(point, composite(color, target.pixel(point))));
+                len += 1;
+
+                if len == BLEND_CHUNK {
+                    break;
+                }
+            }
+        }
+
+        if len == 0 {
+            return Ok(());
+        }
+
+        target.draw_iter(chunk[..len].iter().flatten().copied())?;
+    }
+}
+
+/// Blends the pixels over the background color at the same [`Point`].
+///
+/// When there's no background color, the color is used as it is.
+pub(crate) fn blend_over<C, B, I>(pixels: I, background: B) -> impl Iterator<Item = Pixel<C>>
+where
+    C: AlphaBlend,
+    B: Fn(Point) -> Option<C>,
+    I: IntoIterator<Item = Pixel<Alpha<C>>>,
+{
+    pixels
+        .into_iter()
+        .filter(|Pixel(_, color)| color.alpha != 0)
+        .map(move |Pixel(point, color)| {
+            let blended = match background(point) {
+                Some(background) => color.over(background),
+                None => color.color,
+            };
+
+            Pixel(point, blended)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alpha_blend() {
+        let foreground = Rgb888::new(255, 0, 100);
+        let background = Rgb888::new(0, 255, 100);
+
+        assert_eq!(
+            Rgb888::new(128, 127, 100),
+            foreground.alpha_blend(background, 128)
+        );
+        assert_eq!(background, Alpha::new(foreground, 0).over(background));
+        assert_eq!(foreground, Alpha::opaque(foreground).over(background));
+
+        // channels are blended in their own range
+        assert_eq!(
+            Rgb565::new(16, 32, 0),
+            Rgb565::new(31, 63, 0).alpha_blend(Rgb565::BLACK, 128)
+        );
+        assert_eq!(
+            Gray4::new(8),
+            Gray4::new(15).alpha_blend(Gray4::new(0), 128)
+        );
+        assert_eq!(
+            BinaryColor::Off,
+            BinaryColor::On.alpha_blend(BinaryColor::Off, 127)
+        );
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let foreground = Rgb888::new(255, 128, 0);
+        let background = Rgb888::new(128, 128, 128);
+
+        let blended = |mode| foreground.blend(background, mode);
+        assert_eq!(foreground, blended(BlendMode::Normal));
+        assert_eq!(Rgb888::new(128, 64, 0), blended(BlendMode::Multiply));
+        assert_eq!(Rgb888::new(255, 192, 128), blended(BlendMode::Screen));
+        assert_eq!(Rgb888::new(255, 255, 128), blended(BlendMode::Add));
+        assert_eq!(Rgb888::new(0, 0, 128), blended(BlendMode::Subtract));
+        assert_eq!(Rgb888::new(127, 0, 128), blended(BlendMode::Xor));
+        assert_eq!(Rgb888::new(128, 128, 0), blended(BlendMode::Darken));
+        assert_eq!(Rgb888::new(255, 128, 128), blended(BlendMode::Lighten));
+
+        // channels are blended in their own range
+        assert_eq!(
+            Rgb565::new(31, 63, 0),
+            Rgb565::new(31, 32, 0).blend(Rgb565::new(0, 40, 0), BlendMode::Add)
+        );
+        assert_eq!(
+            Gray4::new(4),
+            Gray4::new(8).blend(Gray4::new(8), BlendMode::Multiply)
+        );
+
+        let on = BinaryColor::On;
+        let off = BinaryColor::Off;
+        assert_eq!(off, on.blend(on, BlendMode::Xor));
+        assert_eq!(on, on.blend(off, BlendMode::Xor));
+        assert_eq!(off, on.blend(off, BlendMode::Multiply));
+        assert_eq!(off, on.blend(on, BlendMode::Subtract));
+        assert_eq!(on, off.blend(on, BlendMode::Screen));
+    }
+}
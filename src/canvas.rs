@@ -1,192 +1,732 @@
-use alloc::{boxed::Box, vec};
-
-use embedded_graphics_core::{prelude::*, primitives::Rectangle};
-
-use crate::utils::center_offset;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use embedded_graphics_core::{
+    geometry::AnchorPoint,
+    image::{GetPixel, ImageDrawable},
+    prelude::*,
+    primitives::Rectangle,
+};
+
+#[cfg(feature = "alloc")]
+use crate::storage::RowMajor;
+use crate::{
+    affine::{self, Affine},
+    blend::{self, Alpha, AlphaBlend, Blend, BlendMode},
+    clip::ClipStack,
+    mask::{ClipMask, WithMask},
+    scale::{self, Filter},
+    scroll::Wrap,
+    storage::{NewPixelStorage, PixelStorage},
+    sub_canvas::SubCanvasMut,
+    utils::{
+        blit_area, center_offset, draw_spans, draw_sub_image, extend_dirty, fill_clipped_rows,
+        Flip, Rotation,
+    },
+};
 
 /// Canvas on which you can draw but it's not drawable on the display yet.
 ///
 /// Draw on the [`Canvas`] using origin of [`Point::zero()`].
+///
+/// The pixels are stored row by row in a [`RowMajor`] storage,
+/// see [`GenericCanvas`] for all the methods of the canvas.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type Canvas<C> = GenericCanvas<RowMajor<C, Box<[Option<C>]>>>;
+
+/// Canvas which is drawable at the provided [`Point`] (location) on the display.
+///
+/// The pixels are stored row by row in a [`RowMajor`] storage,
+/// see [`GenericCanvasAt`] for all the methods of the canvas.
+#[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-#[derive(Clone)]
-pub struct Canvas<C> {
-    /// The size of the [`Canvas`].
-    pub canvas: Size,
-    /// The pixels of the [`Canvas`].
-    pub pixels: Box<[Option<C>]>,
+pub type CanvasAt<C> = GenericCanvasAt<RowMajor<C, Box<[Option<C>]>>>;
+
+/// Canvas on which you can draw which stores the pixels in any [`PixelStorage`].
+///
+/// Every canvas of the crate is a [`GenericCanvas`] over a storage, e.g. [`Canvas`](crate::Canvas)
+/// over a boxed [`RowMajor`](crate::RowMajor) buffer and [`CCanvas`](crate::CCanvas) over an array.
+/// The methods returning canvases of a different size, e.g. [`GenericCanvas::crop`]
+/// and [`GenericCanvas::rotate_90`], are available for the storages implementing
+/// [`NewPixelStorage`].
+///
+/// Draw on the [`GenericCanvas`] using origin of [`Point::zero()`].
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{GenericCanvas, RowMajor};
+/// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+///
+/// let mut framebuffer = [None; 64 * 32];
+/// let storage = RowMajor::new(&mut framebuffer[..], Size::new(64, 32)).unwrap();
+/// let mut canvas = GenericCanvas::from_storage(storage);
+///
+/// Pixel(Point::new(1, 1), Rgb565::RED).draw(&mut canvas)?;
+/// assert_eq!(Some(Rgb565::RED), canvas.get_pixel(Point::new(1, 1)));
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GenericCanvas<S> {
+    storage: S,
 }
 
-impl<C: PixelColor> Canvas<C> {
-    /// Create a new blank [`Canvas`].
+impl<S: PixelStorage> GenericCanvas<S> {
+    /// Create a new [`GenericCanvas`] with the given `storage`,
+    /// whose pixels are kept as they are.
+    pub const fn from_storage(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the storage of the [`GenericCanvas`].
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the storage of the [`GenericCanvas`] for modifying it directly.
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Returns the storage of the [`GenericCanvas`].
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// Returns the color of the pixel at a given [`Point`].
     ///
-    /// # Panics
+    /// Returns [`None`] if the [`Point`] is outside of the [`GenericCanvas`].
+    pub fn get_pixel(&self, point: Point) -> Option<S::Color> {
+        self.get_pixel_at(Point::zero(), point)
+    }
+
+    /// Returns a [`DrawTarget`] which draws on the [`GenericCanvas`] only inside of the `mask`,
+    /// the pixels outside of it are discarded.
     ///
-    /// Panics when width * height > [`usize::MAX`].
-    pub fn new(canvas: Size) -> Self {
-        Self {
-            canvas,
-            pixels: new_pixels(canvas, None),
+    /// The `mask` uses the coordinates of the canvas.
+    pub fn with_mask<'a, M: ClipMask + ?Sized>(&'a mut self, mask: &'a M) -> WithMask<'a, Self, M> {
+        WithMask::new(self, mask)
+    }
+
+    /// Returns a [`DrawTarget`] which draws on the [`GenericCanvas`] only inside of
+    /// up to `N` nested clip areas (see [`ClipStack`]).
+    ///
+    /// The clip areas use the coordinates of the canvas.
+    pub fn clipped_stack<const N: usize>(&mut self) -> ClipStack<'_, Self, N> {
+        ClipStack::new(self)
+    }
+
+    /// Returns the center of [`Size`] of the [`GenericCanvas`].
+    pub fn center(&self) -> Point {
+        Point::zero() + center_offset(self.storage.size())
+    }
+
+    /// Crops the `area` of the [`GenericCanvas`] into the `target`,
+    /// e.g. another [`GenericCanvas`].
+    ///
+    /// The top left corner of the `area` is drawn at [`Point::zero()`] of the `target`
+    /// and only the drawn pixels are copied.
+    pub fn crop_into<D>(&self, area: &Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color>,
+    {
+        draw_sub_image(area, |point| self.get_pixel(point), target)
+    }
+
+    /// Returns a [`SubCanvasMut`] for drawing on the `area` of the [`GenericCanvas`]
+    /// with the origin at the top left corner of the `area`.
+    ///
+    /// The pixels are written directly into the [`GenericCanvas`], only inside of the `area`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_canvas::CCanvas;
+    /// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// let mut screen = CCanvas::<Rgb565, 64, 32>::new();
+    ///
+    /// // the sidebar widget draws using its own coordinates
+    /// let mut sidebar = screen.sub_canvas_mut(&Rectangle::new(Point::new(48, 0), Size::new(16, 32)));
+    /// Pixel(Point::new(0, 0), Rgb565::WHITE).draw(&mut sidebar)?;
+    ///
+    /// assert_eq!(Some(Rgb565::WHITE), screen.get_pixel(Point::new(48, 0)));
+    /// # Ok::<(), core::convert::Infallible>(())
+    /// ```
+    pub fn sub_canvas_mut(&mut self, area: &Rectangle) -> SubCanvasMut<'_, S> {
+        SubCanvasMut::new(*area, &mut self.storage)
+    }
+
+    /// Sets the place with top left offset where the canvas will be drawn to the display.
+    ///
+    /// The pixels are not copied, the [`GenericCanvasAt`] takes the storage.
+    pub fn into_placed_at(self, top_left: Point) -> GenericCanvasAt<S> {
+        GenericCanvasAt {
+            top_left,
+            canvas: self,
         }
     }
 
-    /// Create a [`Canvas`] filled with a default color.
+    /// Sets the center of the [`GenericCanvas`] where it will be drawn to the display.
     ///
-    /// # Panics
+    /// The pixels are not copied, the [`GenericCanvasAt`] takes the storage.
+    pub fn into_placed_center(self, center: Point) -> GenericCanvasAt<S> {
+        let top_left = center - center_offset(self.storage.size());
+
+        self.into_placed_at(top_left)
+    }
+
+    /// Copies the drawn pixels of the `source_area` of the `source` canvas
+    /// to the [`GenericCanvas`] with the top left corner of the area at `destination`.
     ///
-    /// Panics when width * height > [`usize::MAX`].
-    pub fn with_default_color(canvas: Size, default_color: C) -> Self {
-        Self {
-            canvas,
-            pixels: new_pixels(canvas, default_color.into()),
+    /// Transparent pixels of the `source` are skipped and the areas are clipped
+    /// to both canvases. The pixels are copied without drawing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_canvas::CCanvas;
+    /// use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+    ///
+    /// let mut icon = CCanvas::<BinaryColor, 8, 8>::new();
+    /// Pixel(Point::new(1, 1), BinaryColor::On).draw(&mut icon)?;
+    ///
+    /// let mut button = CCanvas::<BinaryColor, 32, 16>::new();
+    /// button.blit(&icon, &icon.bounding_box(), Point::new(4, 4));
+    /// assert_eq!(Some(BinaryColor::On), button.get_pixel(Point::new(5, 5)));
+    /// # Ok::<(), core::convert::Infallible>(())
+    /// ```
+    pub fn blit<T>(
+        &mut self,
+        source: &GenericCanvas<T>,
+        source_area: &Rectangle,
+        destination: Point,
+    ) where
+        T: PixelStorage<Color = S::Color>,
+    {
+        let Some((source_area, top_left)) = blit_area(
+            source.storage.size(),
+            source_area,
+            self.storage.size(),
+            destination,
+        ) else {
+            return;
+        };
+        let offset = top_left - source_area.top_left;
+
+        for point in source_area.points() {
+            if let Some(color) = source.storage.get(point) {
+                self.storage.set(point + offset, Some(color));
+            }
         }
     }
 
-    /// Helper method that returns the index in the array of pixels
-    fn point_to_index(&self, point: Point) -> Option<usize> {
-        point_to_index(self.canvas, Point::zero(), point)
+    /// Scrolls the pixels of the [`GenericCanvas`] in place by `dx` horizontally and `dy` vertically,
+    /// positive values scroll to the right and down.
+    ///
+    /// With [`Wrap::Yes`] the pixels scrolled outside of the [`GenericCanvas`] appear on the
+    /// opposite side, otherwise they are dropped and the uncovered pixels are transparent.
+    pub fn scroll(&mut self, dx: i32, dy: i32, wrap: Wrap) {
+        self.storage.scroll(dx, dy, wrap);
     }
 
-    fn index_to_point(&self, index: usize) -> Option<Point> {
-        index_to_point(self.canvas, index)
+    /// Rotates the [`GenericCanvas`] by 180 degrees in place, without allocating.
+    pub fn rotate_180_in_place(&mut self) {
+        let size = self.storage.size();
+        let width = size.width as usize;
+        if width == 0 {
+            return;
+        }
+
+        // swap the pixels of the first half, row by row, with the ones of the second half
+        let pixel_count = width * size.height as usize;
+        for index in 0..pixel_count / 2 {
+            let point = Point::new((index % width) as i32, (index / width) as i32);
+
+            self.swap_pixels(point, Rotation::Deg180.point(size, point));
+        }
     }
 
-    /// Returns the center of [`Size`] of the [`Canvas`].
-    pub fn center(&self) -> Point {
-        Point::zero() + center_offset(self.canvas)
+    /// Mirrors the [`GenericCanvas`] horizontally in place.
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.flip(Flip::Horizontal);
+    }
+
+    /// Mirrors the [`GenericCanvas`] vertically in place.
+    pub fn flip_vertical_in_place(&mut self) {
+        self.flip(Flip::Vertical);
+    }
+
+    /// Returns the bounding box of the drawn pixels of the [`GenericCanvas`].
+    ///
+    /// Returns [`None`] if no pixels are drawn.
+    pub fn content_bounding_box(&self) -> Option<Rectangle> {
+        content_bounding_box(self.drawn_pixels_at(Point::zero()))
+    }
+
+    /// Returns the bounds of the storage placed at `top_left`.
+    fn bounds_at(&self, top_left: Point) -> Rectangle {
+        Rectangle::new(top_left, self.storage.size())
+    }
+
+    fn get_pixel_at(&self, top_left: Point, point: Point) -> Option<S::Color> {
+        storage_pixel(&self.storage, point - top_left)
+    }
+
+    /// Returns the drawn pixels of the storage placed at `top_left`.
+    fn drawn_pixels_at(&self, top_left: Point) -> impl Iterator<Item = Pixel<S::Color>> + '_ {
+        self.bounds_at(Point::zero())
+            .points()
+            .filter_map(move |point| {
+                self.storage
+                    .get(point)
+                    .map(|color| Pixel(point + top_left, color))
+            })
+    }
+
+    /// Draws the `pixels` on the storage placed at `top_left`.
+    fn draw_iter_at<I>(&mut self, top_left: Point, pixels: I)
+    where
+        I: IntoIterator<Item = Pixel<S::Color>>,
+    {
+        let bounds = self.bounds_at(top_left);
+
+        for Pixel(point, color) in pixels.into_iter() {
+            if bounds.contains(point) {
+                self.storage.set(point - top_left, Some(color));
+            }
+        }
+    }
+
+    /// Fills the `area` of the storage placed at `top_left` with the `colors`.
+    fn fill_contiguous_at<I>(&mut self, top_left: Point, area: &Rectangle, colors: I)
+    where
+        I: IntoIterator<Item = S::Color>,
+    {
+        let bounds = self.bounds_at(top_left);
+        let storage = &mut self.storage;
+
+        fill_clipped_rows(area, &bounds, colors, |row_start, row_colors| {
+            storage.set_row(row_start - top_left, row_colors);
+        });
+    }
+
+    /// Fills the `area` of the storage placed at `top_left` with a solid `color`.
+    fn fill_solid_at(&mut self, top_left: Point, area: &Rectangle, color: S::Color) {
+        let filled = area.intersection(&self.bounds_at(top_left));
+        if filled.is_zero_sized() {
+            return;
+        }
+
+        let relative = Rectangle::new(filled.top_left - top_left, filled.size);
+        self.storage.fill(&relative, Some(color));
+    }
+
+    /// Sets all the pixels of the storage to the `color`.
+    fn clear_storage(&mut self, color: Option<S::Color>) {
+        let bounds = self.bounds_at(Point::zero());
+        if bounds.is_zero_sized() {
+            return;
+        }
+
+        self.storage.fill(&bounds, color);
+    }
+
+    /// Mirrors the storage by swapping the pixels of one half with the mirrored ones.
+    fn flip(&mut self, flip: Flip) {
+        let size = self.storage.size();
+        let half = match flip {
+            Flip::Horizontal => Size::new(size.width / 2, size.height),
+            Flip::Vertical => Size::new(size.width, size.height / 2),
+        };
+
+        for point in Rectangle::new(Point::zero(), half).points() {
+            self.swap_pixels(point, flip.point(size, point));
+        }
+    }
+
+    fn swap_pixels(&mut self, first: Point, second: Point) {
+        let color = self.storage.get(first);
+
+        self.storage.set(first, self.storage.get(second));
+        self.storage.set(second, color);
     }
 }
 
-impl<C: PixelColor> Canvas<C> {
-    /// Create a new cropped [`Canvas`].
+impl<S: PixelStorage + Clone> GenericCanvas<S> {
+    /// Sets the place with top left offset where the canvas will be drawn to the display.
+    pub fn place_at(&self, top_left: Point) -> GenericCanvasAt<S> {
+        self.clone().into_placed_at(top_left)
+    }
+
+    /// Sets the center of the [`GenericCanvas`] where it will be drawn to the display.
+    pub fn place_center(&self, center: Point) -> GenericCanvasAt<S> {
+        self.clone().into_placed_center(center)
+    }
+
+    /// Returns a new [`GenericCanvas`] rotated by 180 degrees.
+    pub fn rotate_180(&self) -> Self {
+        let mut rotated = self.clone();
+        rotated.rotate_180_in_place();
+
+        rotated
+    }
+
+    /// Returns a new [`GenericCanvas`] mirrored horizontally, i.e. the left and right side are swapped.
+    pub fn flip_horizontal(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_horizontal_in_place();
+
+        flipped
+    }
+
+    /// Returns a new [`GenericCanvas`] mirrored vertically, i.e. the top and bottom side are swapped.
+    pub fn flip_vertical(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_vertical_in_place();
+
+        flipped
+    }
+}
+
+impl<S: NewPixelStorage> GenericCanvas<S> {
+    /// Create a new blank [`GenericCanvas`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the storage can't hold the pixels, e.g. width * height > [`usize::MAX`].
+    pub fn new(size: Size) -> Self {
+        Self::from_storage(S::with_size(size, None))
+    }
+
+    /// Create a [`GenericCanvas`] filled with a default color.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the storage can't hold the pixels, e.g. width * height > [`usize::MAX`].
+    pub fn with_default_color(size: Size, default_color: S::Color) -> Self {
+        Self::from_storage(S::with_size(size, Some(default_color)))
+    }
+
+    /// Create a new cropped [`GenericCanvas`].
     ///
     /// This method takes into account the top left [`Point`] of the `area`
-    /// you'd like to crop relative to the [`Canvas`] itself.
-    //
+    /// you'd like to crop relative to the [`GenericCanvas`] itself.
+    ///
     /// If the width or height of the [`Rectangle`] is `0`, this method will
     /// return [`None`] (see [`Rectangle::bottom_right()`])
-    // todo: make safer
-    pub fn crop(&self, area: &Rectangle) -> Option<Canvas<C>> {
-        let mut new = Canvas::new(area.size);
-
+    pub fn crop(&self, area: &Rectangle) -> Option<Self> {
         // returns None when width or height is `0`
-        // it's safe to return `None` for Canvas too!
-        let area_bottom_right = area.bottom_right()?;
-
-        let new_pixels = self.pixels.iter().enumerate().filter_map(|(index, color)| {
-            let color = match color {
-                Some(color) => *color,
-                None => return None,
-            };
-
-            // Canvas always starts from `Point::zero()`
-            let point = self.index_to_point(index).unwrap();
-
-            // for here on, we should compare the point based on the area we want to crop
-            if point >= area.top_left && point <= area_bottom_right {
-                // remove the area top_left to make the origin at `Point::zero()` for the cropped part
-                let pixel = Pixel(point - area.top_left, color);
-
-                Some(pixel)
-            } else {
-                None
-            }
-        });
+        area.bottom_right()?;
 
-        new.draw_iter(new_pixels).ok().map(|_| new)
+        let mut new = Self::new(area.size);
+        self.crop_into(area, &mut new).ok().map(|_| new)
     }
 
-    /// Sets the place with top left offset where the canvas will be drawn to the display.
-    pub fn place_at(&self, top_left: Point) -> CanvasAt<C> {
-        CanvasAt {
-            top_left,
-            canvas: self.canvas,
-            pixels: self.pixels.clone(),
+    /// Returns a new [`GenericCanvas`] cropped to the bounding box of its drawn pixels
+    /// (see [`GenericCanvas::content_bounding_box`]).
+    ///
+    /// Returns [`None`] if no pixels are drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_canvas::Canvas;
+    /// use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+    ///
+    /// let mut canvas = Canvas::new(Size::new(100, 20));
+    /// Pixel(Point::new(10, 5), BinaryColor::On).draw(&mut canvas)?;
+    /// Pixel(Point::new(20, 15), BinaryColor::On).draw(&mut canvas)?;
+    ///
+    /// let trimmed = canvas.trim().unwrap();
+    /// assert_eq!(Size::new(11, 11), trimmed.size());
+    /// // centers the drawn pixels instead of the whole canvas
+    /// let canvas_at = trimmed.place_center(Point::new(50, 50));
+    /// assert_eq!(Point::new(45, 45), canvas_at.top_left);
+    /// # }
+    /// # Ok::<(), core::convert::Infallible>(())
+    /// ```
+    pub fn trim(&self) -> Option<Self> {
+        self.crop(&self.content_bounding_box()?)
+    }
+
+    /// Returns a new [`GenericCanvas`] with the given `size` and the pixels of the canvas
+    /// positioned according to the `anchor`, e.g. [`AnchorPoint::Center`] keeps the
+    /// content centered.
+    ///
+    /// The new areas are transparent and the pixels outside of the new `size` are dropped.
+    pub fn resize(&self, size: Size, anchor: AnchorPoint) -> Self {
+        let area = resized_area(self.storage.size(), size, anchor);
+
+        Self::from_storage(self.copy_area(area, None))
+    }
+
+    /// Same as [`GenericCanvas::resize`] but the new areas are filled with the given `color`.
+    pub fn resize_with_color(&self, size: Size, anchor: AnchorPoint, color: S::Color) -> Self {
+        let area = resized_area(self.storage.size(), size, anchor);
+
+        Self::from_storage(self.copy_area(area, Some(color)))
+    }
+
+    /// Returns a new [`GenericCanvas`] with transparent padding around the pixels of the canvas.
+    ///
+    /// Returns [`None`] if the padded size doesn't fit in a [`Size`]
+    /// or the `top` or `left` padding is larger than [`i32::MAX`].
+    pub fn pad(&self, top: u32, right: u32, bottom: u32, left: u32) -> Option<Self> {
+        let area = padded_area(self.storage.size(), top, right, bottom, left)?;
+
+        Some(Self::from_storage(self.copy_area(area, None)))
+    }
+
+    /// Same as [`GenericCanvas::pad`] but the padding is filled with the given `color`.
+    pub fn pad_with_color(
+        &self,
+        top: u32,
+        right: u32,
+        bottom: u32,
+        left: u32,
+        color: S::Color,
+    ) -> Option<Self> {
+        let area = padded_area(self.storage.size(), top, right, bottom, left)?;
+
+        Some(Self::from_storage(self.copy_area(area, Some(color))))
+    }
+
+    /// Returns a new [`GenericCanvas`] rotated by 90 degrees clockwise.
+    ///
+    /// The width and height of the rotated canvas are swapped.
+    pub fn rotate_90(&self) -> Self {
+        Self::from_storage(self.rotate(Rotation::Deg90))
+    }
+
+    /// Returns a new [`GenericCanvas`] rotated by 270 degrees clockwise
+    /// (90 degrees counterclockwise).
+    ///
+    /// The width and height of the rotated canvas are swapped.
+    pub fn rotate_270(&self) -> Self {
+        Self::from_storage(self.rotate(Rotation::Deg270))
+    }
+
+    /// Rotates the [`GenericCanvas`] by 90 degrees clockwise in place.
+    ///
+    /// The width and height of the canvas are swapped.
+    pub fn rotate_90_in_place(&mut self) {
+        self.storage = self.rotate(Rotation::Deg90);
+    }
+
+    /// Rotates the [`GenericCanvas`] by 270 degrees clockwise in place.
+    ///
+    /// The width and height of the canvas are swapped.
+    pub fn rotate_270_in_place(&mut self) {
+        self.storage = self.rotate(Rotation::Deg270);
+    }
+
+    /// Returns a new [`GenericCanvasAt`] with the pixels of the [`GenericCanvas`] transformed
+    /// by the `affine` transformation (see [`Affine`]).
+    ///
+    /// The new canvas is sized to the bounding box of the transformed canvas
+    /// and its top left corner is the one of the bounding box relative to the origin
+    /// of the canvas. The pixels are resampled using the nearest pixel,
+    /// so transparent pixels stay transparent.
+    ///
+    /// Returns [`None`] if the transformation can't be inverted
+    /// or the transformed bounding box doesn't fit in the [`Point`] coordinates.
+    pub fn transform_affine(&self, affine: &Affine) -> Option<GenericCanvasAt<S>> {
+        transform_affine(affine, &self.storage, Point::zero(), |size| {
+            S::with_size(size, None)
+        })
+    }
+
+    /// Returns a new [`GenericCanvas`] scaled to the given `size` using the nearest pixels.
+    ///
+    /// Works with any color, for smoother results with colors implementing
+    /// [`AlphaBlend`] use [`GenericCanvas::scale_to`] with [`Filter::Bilinear`].
+    pub fn scale_nearest(&self, size: Size) -> Self {
+        self.scale(size, |point| {
+            self.get_pixel(scale::nearest(self.storage.size(), size, point))
+        })
+    }
+
+    /// Returns a new storage for the `area` relative to the canvas with the pixels
+    /// of the canvas, the pixels outside of the canvas are set to `fill`.
+    fn copy_area(&self, area: Rectangle, fill: Option<S::Color>) -> S {
+        let mut copy = S::with_size(area.size, fill);
+
+        let offset = -area.top_left;
+        let copied = self
+            .bounds_at(offset)
+            .intersection(&Rectangle::new(Point::zero(), area.size));
+        for point in copied.points() {
+            copy.set(point, self.storage.get(point - offset));
         }
+
+        copy
     }
 
-    /// Sets the center of the [`Canvas`] where it will be drawn to the display.
-    pub fn place_center(&self, center: Point) -> CanvasAt<C> {
-        let top_left = center - center_offset(self.canvas);
+    fn rotate(&self, rotation: Rotation) -> S {
+        let mut rotated = S::with_size(rotation.size(self.storage.size()), None);
+        rotate_into(&self.storage, &mut rotated, rotation);
 
-        self.place_at(top_left)
+        rotated
+    }
+
+    fn scale<F>(&self, size: Size, sample: F) -> Self
+    where
+        F: Fn(Point) -> Option<S::Color>,
+    {
+        let mut scaled = S::with_size(size, None);
+
+        for point in Rectangle::new(Point::zero(), size).points() {
+            if let Some(color) = sample(point) {
+                scaled.set(point, Some(color));
+            }
+        }
+
+        Self::from_storage(scaled)
     }
 }
 
-impl<C> OriginDimensions for Canvas<C> {
+impl<S> GenericCanvas<S>
+where
+    S: NewPixelStorage,
+    S::Color: AlphaBlend,
+{
+    /// Returns a new [`GenericCanvas`] scaled to the given `size` using the `filter`.
+    ///
+    /// Transparent pixels stay transparent and with [`Filter::Bilinear`]
+    /// the edge pixels are interpolated only between the drawn neighbours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use embedded_canvas::{Canvas, Filter};
+    /// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// let mut icon = Canvas::new(Size::new(16, 16));
+    /// Pixel(Point::new(8, 8), Rgb565::RED).draw(&mut icon)?;
+    ///
+    /// let scaled = icon.scale_to(Size::new(24, 24), Filter::Bilinear);
+    /// assert_eq!(Size::new(24, 24), scaled.size());
+    /// # }
+    /// # Ok::<(), core::convert::Infallible>(())
+    /// ```
+    pub fn scale_to(&self, size: Size, filter: Filter) -> Self {
+        match filter {
+            Filter::Nearest => self.scale_nearest(size),
+            Filter::Bilinear => self.scale(size, |point| {
+                scale::bilinear(self.storage.size(), size, point, |point| {
+                    self.get_pixel(point)
+                })
+            }),
+        }
+    }
+}
+
+impl<S: PixelStorage> OriginDimensions for GenericCanvas<S> {
     fn size(&self) -> Size {
-        self.canvas
+        self.storage.size()
     }
 }
 
-impl<C: PixelColor> DrawTarget for Canvas<C> {
-    type Color = C;
+impl<S: PixelStorage> GetPixel for GenericCanvas<S> {
+    type Color = S::Color;
+
+    fn pixel(&self, point: Point) -> Option<Self::Color> {
+        self.get_pixel(point)
+    }
+}
+
+impl<S: PixelStorage> DrawTarget for GenericCanvas<S> {
+    type Color = S::Color;
     type Error = core::convert::Infallible;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for Pixel(point, color) in pixels.into_iter() {
-            if let Some(index) = self.point_to_index(point) {
-                self.pixels[index] = Some(color);
-            }
-        }
+        self.draw_iter_at(Point::zero(), pixels);
 
         Ok(())
     }
-}
 
-/// Canvas which is drawable at the provided [`Point`] (location) on the display.
-#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-#[derive(Debug, Clone)]
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.fill_contiguous_at(Point::zero(), area, colors);
 
-pub struct CanvasAt<C> {
-    /// The top left offset where the [`CanvasAt`] will be drawn to the display.
-    pub top_left: Point,
-    /// The size of the [`CanvasAt`].
-    pub canvas: Size,
-    /// The pixels of the [`CanvasAt`].
-    pub pixels: Box<[Option<C>]>,
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid_at(Point::zero(), area, color);
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_storage(Some(color));
+
+        Ok(())
+    }
 }
 
-impl<C: PixelColor> CanvasAt<C> {
-    /// Create a new blank [`CanvasAt`].
-    ///
-    /// # Panics
-    ///
-    /// Panics when width * height > [`usize::MAX`].
-    pub fn new(top_left: Point, canvas: Size) -> Self {
-        let pixels = new_pixels(canvas, None);
+impl<S: PixelStorage> ImageDrawable for GenericCanvas<S> {
+    type Color = S::Color;
 
-        Self {
-            top_left,
-            canvas,
-            pixels,
-        }
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        draw_spans(&self.bounding_box(), |point| self.get_pixel(point), target)
     }
 
-    /// Create a [`CanvasAt`] filled with a default color.
-    ///
-    /// # Panics
-    ///
-    /// Panics when width * height > [`usize::MAX`].
-    pub fn with_default_color(top_left: Point, canvas: Size, default_color: C) -> Self {
-        let pixel_count = canvas.width as usize * canvas.height as usize;
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        draw_sub_image(area, |point| self.get_pixel(point), target)
+    }
+}
 
-        let pixels = vec![Some(default_color); pixel_count].into_boxed_slice();
+/// Canvas which stores the pixels in any [`PixelStorage`] and
+/// is drawable at the provided [`Point`] (location) on the display.
+///
+/// Every canvas of the crate which is drawable on the display is a [`GenericCanvasAt`]
+/// over a storage, e.g. [`CanvasAt`](crate::CanvasAt) and [`CCanvasAt`](crate::CCanvasAt).
+#[derive(Debug, Clone, Copy)]
+pub struct GenericCanvasAt<S> {
+    /// The top left offset where the [`GenericCanvasAt`] will be drawn to the display.
+    pub top_left: Point,
+    canvas: GenericCanvas<S>,
+}
 
+impl<S: PixelStorage> GenericCanvasAt<S> {
+    /// Create a new [`GenericCanvasAt`] with the given `storage`,
+    /// whose pixels are kept as they are.
+    pub const fn from_storage(top_left: Point, storage: S) -> Self {
         Self {
             top_left,
-            canvas,
-            pixels,
+            canvas: GenericCanvas::from_storage(storage),
         }
     }
 
-    /// Create a new blank [`CanvasAt`] with a set center on the display.
-    pub fn with_center(center: Point, canvas: Size) -> Self {
-        let top_left = center - center_offset(canvas);
+    /// Returns the storage of the [`GenericCanvasAt`].
+    pub fn storage(&self) -> &S {
+        self.canvas.storage()
+    }
 
-        Self::new(top_left, canvas)
+    /// Returns the storage of the [`GenericCanvasAt`] for modifying it directly.
+    pub fn storage_mut(&mut self) -> &mut S {
+        self.canvas.storage_mut()
+    }
+
+    /// Returns the storage of the [`GenericCanvasAt`].
+    pub fn into_storage(self) -> S {
+        self.canvas.into_storage()
     }
 
     /// Returns the center of the bounding box.
@@ -196,113 +736,439 @@ impl<C: PixelColor> CanvasAt<C> {
 
     /// Returns the color of the pixel at a given [`Point`].
     ///
-    /// Returns [`None`] if the [`Point`] is outside of the [`CanvasAt`].
-    pub fn get_pixel(&self, point: Point) -> Option<C> {
-        self.point_to_index(point)
-            .and_then(|index| self.pixels.get(index).copied().flatten())
+    /// Returns [`None`] if the [`Point`] is outside of the [`GenericCanvasAt`].
+    pub fn get_pixel(&self, point: Point) -> Option<S::Color> {
+        self.canvas.get_pixel_at(self.top_left, point)
+    }
+
+    /// Returns a [`DrawTarget`] which draws on the [`GenericCanvasAt`] only inside of the `mask`,
+    /// the pixels outside of it are discarded.
+    ///
+    /// The `mask` uses the coordinates of the display.
+    pub fn with_mask<'a, M: ClipMask + ?Sized>(&'a mut self, mask: &'a M) -> WithMask<'a, Self, M> {
+        WithMask::new(self, mask)
+    }
+
+    /// Returns a [`DrawTarget`] which draws on the [`GenericCanvasAt`] only inside of
+    /// up to `N` nested clip areas (see [`ClipStack`]).
+    ///
+    /// The clip areas use the coordinates of the display.
+    pub fn clipped_stack<const N: usize>(&mut self) -> ClipStack<'_, Self, N> {
+        ClipStack::new(self)
+    }
+
+    /// Crops the `area` of the [`GenericCanvasAt`] relative to the **display** into the `target`,
+    /// e.g. a [`GenericCanvasAt`] placed at the top left corner of the `area`.
+    ///
+    /// Only the drawn pixels are copied, at the same location on the display.
+    pub fn crop_into<D>(&self, area: &Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color>,
+    {
+        draw_spans(
+            &area.intersection(&self.bounding_box()),
+            |point| self.get_pixel(point),
+            target,
+        )
+    }
+
+    /// Copies the drawn pixels of the `source_area` of the `source` canvas to the
+    /// [`GenericCanvasAt`] with the top left corner of the area at `destination` on the display.
+    ///
+    /// Transparent pixels of the `source` are skipped and the areas are clipped
+    /// to both canvases. The pixels are copied without drawing them.
+    pub fn blit<T>(
+        &mut self,
+        source: &GenericCanvas<T>,
+        source_area: &Rectangle,
+        destination: Point,
+    ) where
+        T: PixelStorage<Color = S::Color>,
+    {
+        let destination = destination - self.top_left;
+
+        self.canvas.blit(source, source_area, destination);
+    }
+
+    /// Scrolls the pixels of the [`GenericCanvasAt`] in place by `dx` horizontally and `dy` vertically,
+    /// positive values scroll to the right and down.
+    ///
+    /// With [`Wrap::Yes`] the pixels scrolled outside of the [`GenericCanvasAt`] appear on the
+    /// opposite side, otherwise they are dropped and the uncovered pixels are transparent.
+    pub fn scroll(&mut self, dx: i32, dy: i32, wrap: Wrap) {
+        self.canvas.scroll(dx, dy, wrap);
+    }
+
+    /// Rotates the [`GenericCanvasAt`] by 180 degrees in place, without allocating.
+    pub fn rotate_180_in_place(&mut self) {
+        self.canvas.rotate_180_in_place();
+    }
+
+    /// Mirrors the [`GenericCanvasAt`] horizontally in place.
+    ///
+    /// The [`GenericCanvasAt`] is mirrored around its own bounding box.
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.canvas.flip_horizontal_in_place();
+    }
+
+    /// Mirrors the [`GenericCanvasAt`] vertically in place.
+    ///
+    /// The [`GenericCanvasAt`] is mirrored around its own bounding box.
+    pub fn flip_vertical_in_place(&mut self) {
+        self.canvas.flip_vertical_in_place();
+    }
+
+    /// Returns the bounding box of the drawn pixels of the [`GenericCanvasAt`] on the display.
+    ///
+    /// Returns [`None`] if no pixels are drawn.
+    pub fn content_bounding_box(&self) -> Option<Rectangle> {
+        content_bounding_box(self.drawn_pixels())
+    }
+
+    /// Returns the drawn pixels of the [`GenericCanvasAt`] on the display.
+    fn drawn_pixels(&self) -> impl Iterator<Item = Pixel<S::Color>> + '_ {
+        self.canvas.drawn_pixels_at(self.top_left)
+    }
+}
+
+impl<S: PixelStorage + Clone> GenericCanvasAt<S> {
+    /// Returns a new [`GenericCanvasAt`] rotated by 180 degrees.
+    pub fn rotate_180(&self) -> Self {
+        let mut rotated = self.clone();
+        rotated.rotate_180_in_place();
+
+        rotated
+    }
+
+    /// Returns a new [`GenericCanvasAt`] mirrored horizontally, i.e. the left and right side are swapped.
+    ///
+    /// The [`GenericCanvasAt`] is mirrored around its own bounding box.
+    pub fn flip_horizontal(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_horizontal_in_place();
+
+        flipped
+    }
+
+    /// Returns a new [`GenericCanvasAt`] mirrored vertically, i.e. the top and bottom side are swapped.
+    ///
+    /// The [`GenericCanvasAt`] is mirrored around its own bounding box.
+    pub fn flip_vertical(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_vertical_in_place();
+
+        flipped
+    }
+}
+
+impl<S: NewPixelStorage> GenericCanvasAt<S> {
+    /// Create a new blank [`GenericCanvasAt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the storage can't hold the pixels, e.g. width * height > [`usize::MAX`].
+    pub fn new(top_left: Point, size: Size) -> Self {
+        GenericCanvas::<S>::new(size).into_placed_at(top_left)
     }
 
-    /// Helper method that returns the index in the array of pixels
-    fn point_to_index(&self, point: Point) -> Option<usize> {
-        point_to_index(self.canvas, self.top_left, point)
+    /// Create a [`GenericCanvasAt`] filled with a default color.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the storage can't hold the pixels, e.g. width * height > [`usize::MAX`].
+    pub fn with_default_color(top_left: Point, size: Size, default_color: S::Color) -> Self {
+        GenericCanvas::<S>::with_default_color(size, default_color).into_placed_at(top_left)
     }
 
-    fn index_to_point(&self, index: usize) -> Option<Point> {
-        // we account for the displacement of the current Canvas
-        index_to_point(self.canvas, index).map(|point| point + self.top_left)
+    /// Create a new blank [`GenericCanvasAt`] with a set center on the display.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the storage can't hold the pixels, e.g. width * height > [`usize::MAX`].
+    pub fn with_center(center: Point, size: Size) -> Self {
+        GenericCanvas::<S>::new(size).into_placed_center(center)
     }
 
-    /// Create a new cropped [`CanvasAt`].
+    /// Create a new cropped [`GenericCanvasAt`].
     ///
     /// This method takes into account the top left [`Point`] of the `area`
     /// you'd like to crop relative to the **display**.
     ///
     /// If the width or height of the [`Rectangle`] is `0`, this method will
     /// return [`None`] (see [`Rectangle::bottom_right()`])
-    // todo: make safer
-    pub fn crop(&self, area: &Rectangle) -> Option<CanvasAt<C>> {
-        let mut new = CanvasAt::new(area.top_left, area.size);
-
+    pub fn crop(&self, area: &Rectangle) -> Option<Self> {
         // returns None when width or height is `0`
-        // it's safe to return `None` for Canvas too!
-        let area_bottom_right = area.bottom_right()?;
+        area.bottom_right()?;
 
-        let new_pixels = self.pixels.iter().enumerate().filter_map(|(index, color)| {
-            let color = match color {
-                Some(color) => *color,
-                None => return None,
-            };
+        let mut new = Self::new(area.top_left, area.size);
+        self.crop_into(area, &mut new).ok().map(|_| new)
+    }
 
-            let point = self.index_to_point(index).expect("Will never fail");
+    /// Returns a new [`GenericCanvasAt`] cropped to the bounding box of its drawn pixels
+    /// (see [`GenericCanvasAt::content_bounding_box`]), the drawn pixels stay at the same
+    /// location on the display.
+    ///
+    /// Returns [`None`] if no pixels are drawn.
+    pub fn trim(&self) -> Option<Self> {
+        self.crop(&self.content_bounding_box()?)
+    }
 
-            // for here on, we should compare the point based on the area we want to crop
-            if point >= area.top_left && point <= area_bottom_right {
-                let pixel = Pixel(point, color);
+    /// Returns a new [`GenericCanvasAt`] with the given `size` and the pixels of the canvas
+    /// positioned according to the `anchor`, e.g. [`AnchorPoint::Center`] keeps the
+    /// content centered. The pixels stay at the same location on the display.
+    ///
+    /// The new areas are transparent and the pixels outside of the new `size` are dropped.
+    pub fn resize(&self, size: Size, anchor: AnchorPoint) -> Self {
+        self.copy_area(resized_area(self.canvas.size(), size, anchor), None)
+    }
 
-                Some(pixel)
-            } else {
-                None
-            }
-        });
+    /// Same as [`GenericCanvasAt::resize`] but the new areas are filled with the given `color`.
+    pub fn resize_with_color(&self, size: Size, anchor: AnchorPoint, color: S::Color) -> Self {
+        self.copy_area(resized_area(self.canvas.size(), size, anchor), Some(color))
+    }
+
+    /// Returns a new [`GenericCanvasAt`] with transparent padding around the pixels of the canvas,
+    /// the pixels stay at the same location on the display.
+    ///
+    /// Returns [`None`] if the padded size doesn't fit in a [`Size`]
+    /// or the `top` or `left` padding is larger than [`i32::MAX`].
+    pub fn pad(&self, top: u32, right: u32, bottom: u32, left: u32) -> Option<Self> {
+        let area = padded_area(self.canvas.size(), top, right, bottom, left)?;
+
+        Some(self.copy_area(area, None))
+    }
+
+    /// Same as [`GenericCanvasAt::pad`] but the padding is filled with the given `color`.
+    pub fn pad_with_color(
+        &self,
+        top: u32,
+        right: u32,
+        bottom: u32,
+        left: u32,
+        color: S::Color,
+    ) -> Option<Self> {
+        let area = padded_area(self.canvas.size(), top, right, bottom, left)?;
+
+        Some(self.copy_area(area, Some(color)))
+    }
+
+    /// Returns a new [`GenericCanvasAt`] rotated by 90 degrees clockwise.
+    ///
+    /// The top left corner stays the same and
+    /// the width and height of the rotated canvas are swapped.
+    pub fn rotate_90(&self) -> Self {
+        Self::from_storage(self.top_left, self.canvas.rotate(Rotation::Deg90))
+    }
+
+    /// Returns a new [`GenericCanvasAt`] rotated by 270 degrees clockwise
+    /// (90 degrees counterclockwise).
+    ///
+    /// The top left corner stays the same and
+    /// the width and height of the rotated canvas are swapped.
+    pub fn rotate_270(&self) -> Self {
+        Self::from_storage(self.top_left, self.canvas.rotate(Rotation::Deg270))
+    }
+
+    /// Rotates the [`GenericCanvasAt`] by 90 degrees clockwise in place.
+    ///
+    /// The width and height of the canvas are swapped.
+    pub fn rotate_90_in_place(&mut self) {
+        self.canvas.rotate_90_in_place();
+    }
+
+    /// Rotates the [`GenericCanvasAt`] by 270 degrees clockwise in place.
+    ///
+    /// The width and height of the canvas are swapped.
+    pub fn rotate_270_in_place(&mut self) {
+        self.canvas.rotate_270_in_place();
+    }
 
-        new.draw_iter(new_pixels).ok().map(|_| new)
+    /// Returns a new [`GenericCanvasAt`] with the pixels of the canvas transformed
+    /// by the `affine` transformation (see [`Affine`]) relative to its top left corner.
+    ///
+    /// The new canvas is sized to the bounding box of the transformed canvas.
+    /// The pixels are resampled using the nearest pixel, so transparent pixels stay transparent.
+    ///
+    /// Returns [`None`] if the transformation can't be inverted
+    /// or the transformed bounding box doesn't fit in the [`Point`] coordinates.
+    pub fn transform_affine(&self, affine: &Affine) -> Option<Self> {
+        transform_affine(affine, self.storage(), self.top_left, |size| {
+            S::with_size(size, None)
+        })
+    }
+
+    /// Returns a new [`GenericCanvasAt`] for the `area` relative to the canvas
+    /// with the pixels of the canvas at the same location on the display.
+    fn copy_area(&self, area: Rectangle, fill: Option<S::Color>) -> Self {
+        let storage = self.canvas.copy_area(area, fill);
+
+        Self::from_storage(self.top_left + area.top_left, storage)
     }
 }
 
-impl<C: PixelColor> Dimensions for CanvasAt<C> {
+impl<S: PixelStorage> Dimensions for GenericCanvasAt<S> {
     fn bounding_box(&self) -> Rectangle {
-        Rectangle::new(self.top_left, self.canvas)
+        self.canvas.bounds_at(self.top_left)
     }
 }
 
-impl<C: PixelColor> DrawTarget for CanvasAt<C> {
-    type Color = C;
+impl<S: PixelStorage> GetPixel for GenericCanvasAt<S> {
+    type Color = S::Color;
+
+    fn pixel(&self, point: Point) -> Option<Self::Color> {
+        self.get_pixel(point)
+    }
+}
+
+impl<S: PixelStorage> DrawTarget for GenericCanvasAt<S> {
+    type Color = S::Color;
     type Error = core::convert::Infallible;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for Pixel(point, color) in pixels.into_iter() {
-            if let Some(index) = self.point_to_index(point) {
-                self.pixels[index] = Some(color);
-            }
-        }
+        self.canvas.draw_iter_at(self.top_left, pixels);
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.canvas.fill_contiguous_at(self.top_left, area, colors);
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.canvas.fill_solid_at(self.top_left, area, color);
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.canvas.clear_storage(Some(color));
 
         Ok(())
     }
 }
 
-impl<C> Drawable for CanvasAt<C>
-where
-    C: PixelColor,
-{
-    type Color = C;
+impl<S: PixelStorage> Drawable for GenericCanvasAt<S> {
+    type Color = S::Color;
     type Output = ();
 
     fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = S::Color>,
+    {
+        draw_spans(&self.bounding_box(), |point| self.get_pixel(point), target)
+    }
+}
+
+impl<S> GenericCanvasAt<S>
+where
+    S: PixelStorage,
+    S::Color: Blend,
+{
+    /// Draws the [`GenericCanvasAt`] on the display by compositing each pixel over the
+    /// pixel already drawn on the display using the blend `mode`.
+    ///
+    /// The display pixels are read back using [`GetPixel`], pixels over
+    /// undrawn display pixels are drawn as they are and
+    /// pixels outside of the display are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_canvas::{BlendMode, CCanvasAt};
+    /// use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+    ///
+    /// let mut display = CCanvasAt::<_, 8, 8>::with_default_color(Point::zero(), BinaryColor::On);
+    ///
+    /// // invert the pixels of the display below the cursor
+    /// let cursor = CCanvasAt::<_, 1, 4>::with_default_color(Point::new(2, 2), BinaryColor::On);
+    /// cursor.draw_with_blend(&mut display, BlendMode::Xor)?;
+    /// assert_eq!(Some(BinaryColor::Off), display.get_pixel(Point::new(2, 3)));
+    /// # Ok::<(), core::convert::Infallible>(())
+    /// ```
+    pub fn draw_with_blend<D>(&self, target: &mut D, mode: BlendMode) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color> + GetPixel<Color = S::Color>,
+    {
+        blend::draw_with_blend(self.drawn_pixels(), target, mode)
+    }
+}
+
+impl<C, S> GenericCanvasAt<S>
+where
+    C: AlphaBlend,
+    S: PixelStorage<Color = Alpha<C>>,
+{
+    /// Draws the [`GenericCanvasAt`] on the display by blending each pixel over
+    /// the pixel already drawn on the display.
+    ///
+    /// The display pixels are read back using [`GetPixel`], pixels over
+    /// undrawn display pixels are drawn without blending and
+    /// pixels outside of the display are skipped.
+    pub fn draw_blended<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C> + GetPixel<Color = C>,
+    {
+        blend::draw_blended(self.drawn_pixels(), target)
+    }
+
+    /// Draws the [`GenericCanvasAt`] on the display by blending each pixel over
+    /// the pixel of the `background` canvas at the same location on the display.
+    ///
+    /// Pixels which are not drawn on the `background` are drawn without blending.
+    pub fn draw_blended_over<D, B>(
+        &self,
+        background: &GenericCanvasAt<B>,
+        target: &mut D,
+    ) -> Result<(), D::Error>
     where
         D: DrawTarget<Color = C>,
+        B: PixelStorage<Color = C>,
     {
-        let pixels_iter = self.bounding_box().points().filter_map(|point| {
-            // for the drawing position we need to account for the top_left offset of the drawn display
-            self.get_pixel(point).map(|color| Pixel(point, color))
-        });
+        target.draw_iter(blend::blend_over(self.drawn_pixels(), |point| {
+            background.get_pixel(point)
+        }))
+    }
+}
+
+#[cfg(feature = "transform")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transform")))]
+impl<S: PixelStorage + Clone> embedded_graphics::transform::Transform for GenericCanvas<S> {
+    /// Translates the content of the [`GenericCanvas`] by the given [`Point`].
+    ///
+    /// The size of the [`GenericCanvas`] stays the same, pixels translated outside
+    /// of the [`GenericCanvas`] are dropped and the uncovered pixels are transparent.
+    fn translate(&self, by: Point) -> Self {
+        let mut translated = self.clone();
+        translated.clear_storage(None);
+        translated.draw_iter_at(Point::zero(), self.drawn_pixels_at(by));
 
-        target.draw_iter(pixels_iter)
+        translated
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        *self = self.translate(by);
+
+        self
     }
 }
 
 #[cfg(feature = "transform")]
 #[cfg_attr(docsrs, doc(cfg(feature = "transform")))]
-impl<C: PixelColor> embedded_graphics::transform::Transform for CanvasAt<C> {
+impl<S: PixelStorage + Clone> embedded_graphics::transform::Transform for GenericCanvasAt<S> {
     fn translate(&self, by: Point) -> Self {
-        Self {
-            // update the CanvasAt top_left!
-            top_left: self.top_left + by,
-            canvas: self.canvas,
-            pixels: self.pixels.clone(),
-        }
+        let mut translated = self.clone();
+        // update the GenericCanvasAt top_left!
+        translated.top_left += by;
+
+        translated
     }
 
     fn translate_mut(&mut self, by: Point) -> &mut Self {
@@ -312,105 +1178,696 @@ impl<C: PixelColor> embedded_graphics::transform::Transform for CanvasAt<C> {
     }
 }
 
-/// Generic function that will take into account the top_left offset when returning the index
-// TODO: make safer
-fn point_to_index(size: Size, top_left_offset: Point, point: Point) -> Option<usize> {
-    // we must account for the top_left corner of the drawing box
-    if let Ok((x, y)) = <(u32, u32)>::try_from(point - top_left_offset) {
-        if x < size.width && y < size.height {
-            return Some((x + y * size.width) as usize);
-        }
+/// Returns the color of the pixel of the `storage` at a given [`Point`].
+///
+/// Returns [`None`] if the [`Point`] is outside of the `storage`.
+pub(crate) fn storage_pixel<S: PixelStorage>(storage: &S, point: Point) -> Option<S::Color> {
+    if Rectangle::new(Point::zero(), storage.size()).contains(point) {
+        storage.get(point)
+    } else {
+        None
     }
+}
+
+/// Returns the area relative to a canvas with the given `size`
+/// of the canvas resized to `new_size` according to the `anchor`.
+fn resized_area(size: Size, new_size: Size, anchor: AnchorPoint) -> Rectangle {
+    Rectangle::new(Point::zero(), size).resized(new_size, anchor)
+}
+
+/// Returns the area relative to a canvas with the given `size` of the padded canvas.
+///
+/// Returns [`None`] if the padded size overflows or the `top` or `left` padding
+/// doesn't fit in an [`i32`].
+fn padded_area(size: Size, top: u32, right: u32, bottom: u32, left: u32) -> Option<Rectangle> {
+    let width = size.width.checked_add(left)?.checked_add(right)?;
+    let height = size.height.checked_add(top)?.checked_add(bottom)?;
+    let top_left = Point::new(-i32::try_from(left).ok()?, -i32::try_from(top).ok()?);
+
+    Some(Rectangle::new(top_left, Size::new(width, height)))
+}
 
-    None
+/// Returns the bounding box of the `pixels`.
+fn content_bounding_box<C: PixelColor>(
+    pixels: impl Iterator<Item = Pixel<C>>,
+) -> Option<Rectangle> {
+    pixels.fold(None, |mut bounding_box, Pixel(point, _color)| {
+        extend_dirty(&mut bounding_box, Rectangle::new(point, Size::new(1, 1)));
+
+        bounding_box
+    })
 }
 
-fn index_to_point(size: Size, index: usize) -> Option<Point> {
-    let x = index as i32 % size.width as i32;
-    let y = index as i32 / size.width as i32;
-    let point = Point { x, y };
+/// Copies the pixels of the `source` storage rotated by `rotation` to the `target` storage,
+/// whose size must be the rotated size.
+pub(crate) fn rotate_into<S, T>(source: &S, target: &mut T, rotation: Rotation)
+where
+    S: PixelStorage,
+    T: PixelStorage<Color = S::Color>,
+{
+    let size = source.size();
 
-    Some(point)
+    for point in Rectangle::new(Point::zero(), size).points() {
+        target.set(rotation.point(size, point), source.get(point));
+    }
 }
 
-fn new_pixels<C: PixelColor>(size: Size, color: Option<C>) -> Box<[Option<C>]> {
-    let pixel_count = size.width as usize * size.height as usize;
+/// Returns a new [`GenericCanvasAt`] with the pixels of the `storage`
+/// transformed by the `affine` transformation and placed relative to `top_left`.
+///
+/// The storage of the new canvas is created by `new_storage` for the size of the
+/// bounding box, transformed pixels outside of the created storage are dropped.
+pub(crate) fn transform_affine<S, T, F>(
+    affine: &Affine,
+    storage: &S,
+    top_left: Point,
+    new_storage: F,
+) -> Option<GenericCanvasAt<T>>
+where
+    S: PixelStorage,
+    T: PixelStorage<Color = S::Color>,
+    F: FnOnce(Size) -> T,
+{
+    let inverse = affine.inverse()?;
+    let area = affine.bounding_box(storage.size())?;
+
+    let mut transformed =
+        GenericCanvasAt::from_storage(top_left + area.top_left, new_storage(area.size));
+    let pixels = Rectangle::new(area.top_left, transformed.storage().size())
+        .points()
+        .filter_map(|point| {
+            storage_pixel(storage, affine::source_point(&inverse, point))
+                .map(|color| Pixel(point + top_left, color))
+        });
+    // drawing on a canvas is infallible
+    let _ = transformed.draw_iter(pixels);
 
-    vec![color; pixel_count].into_boxed_slice()
+    Some(transformed)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod test {
+    use alloc::vec::Vec;
+
     use embedded_graphics_core::pixelcolor::BinaryColor;
 
     use super::*;
 
     #[test]
-    fn test_index_to_point() {
-        let canvas = Canvas::<BinaryColor>::new(Size {
+    fn test_row_major_pixels() {
+        let mut canvas = Canvas::<BinaryColor>::new(Size {
             width: 320,
             height: 240,
         });
 
-        {
-            let center = Point::new(160, 120);
-            let center_index = canvas.point_to_index(center).expect("Inside the canvas");
+        let corners = [
+            (Point::new(0, 0), 0),
+            (Point::new(320 - 1, 0), 320 - 1),
+            (Point::new(0, 240 - 1), 320 * (240 - 1)),
+            (Point::new(320 - 1, 240 - 1), 320 * 240 - 1),
+            (Point::new(160, 120), 320 * 120 + 160),
+        ];
+        for (point, index) in corners {
+            Pixel(point, BinaryColor::On).draw(&mut canvas).unwrap();
+
+            assert_eq!(Some(BinaryColor::On), canvas.storage().pixels()[index]);
+        }
+        assert_eq!(5, canvas.storage().pixels().iter().flatten().count());
+    }
+
+    #[test]
+    fn test_draw_blended_over() {
+        use embedded_graphics_core::pixelcolor::Rgb888;
+
+        let area = Rectangle::new(Point::new(2, 2), Size::new(2, 1));
+        let background = CanvasAt::with_default_color(area.top_left, area.size, Rgb888::BLACK);
+
+        let mut canvas = CanvasAt::new(Point::new(2, 2), Size::new(3, 1));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(2, 2), Alpha::new(Rgb888::WHITE, 128)),
+                Pixel(Point::new(3, 2), Alpha::new(Rgb888::WHITE, 0)),
+                // outside of the background
+                Pixel(Point::new(4, 2), Alpha::new(Rgb888::RED, 10)),
+            ])
+            .unwrap();
+
+        let mut target = CanvasAt::new(Point::zero(), Size::new(10, 10));
+        canvas.draw_blended_over(&background, &mut target).unwrap();
+
+        assert_eq!(
+            Some(Rgb888::new(128, 128, 128)),
+            target.get_pixel(Point::new(2, 2))
+        );
+        assert_eq!(None, target.get_pixel(Point::new(3, 2)));
+        assert_eq!(Some(Rgb888::RED), target.get_pixel(Point::new(4, 2)));
+    }
+
+    #[test]
+    fn test_draw_blended() {
+        use embedded_graphics_core::pixelcolor::Rgb888;
+
+        let mut canvas = CanvasAt::new(Point::new(-1, 0), Size::new(3, 1));
+        canvas
+            .fill_solid(&canvas.bounding_box(), Alpha::new(Rgb888::WHITE, 128))
+            .unwrap();
+
+        // only the left pixel of the target is drawn
+        let mut target = CanvasAt::new(Point::zero(), Size::new(4, 1));
+        Pixel(Point::zero(), Rgb888::BLACK)
+            .draw(&mut target)
+            .unwrap();
+        canvas.draw_blended(&mut target).unwrap();
+
+        assert_eq!(
+            Some(Rgb888::new(128, 128, 128)),
+            target.get_pixel(Point::zero())
+        );
+        // drawn over the undrawn pixel without blending
+        assert_eq!(Some(Rgb888::WHITE), target.get_pixel(Point::new(1, 0)));
+        assert_eq!(None, target.get_pixel(Point::new(2, 0)));
+    }
+
+    #[test]
+    fn test_fill_contiguous_and_solid() {
+        use embedded_graphics_core::pixelcolor::Gray8;
+
+        // partially outside of the canvas on every side
+        let area = Rectangle::new(Point::new(3, -2), Size::new(6, 9));
+        let colors = (0..=u8::MAX).map(Gray8::new);
+
+        let mut expected = CanvasAt::new(Point::new(4, -1), Size::new(4, 5));
+        expected
+            .draw_iter(
+                area.points()
+                    .zip(colors.clone())
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+            .unwrap();
+
+        let mut canvas = CanvasAt::new(Point::new(4, -1), Size::new(4, 5));
+        canvas.fill_contiguous(&area, colors.clone()).unwrap();
+        assert_eq!(expected.storage().pixels(), canvas.storage().pixels());
+
+        // less colors than pixels in the area
+        let mut canvas = Canvas::new(Size::new(4, 5));
+        canvas.fill_contiguous(&area, colors.take(20)).unwrap();
+        assert_eq!(None, canvas.storage().pixels()[0]);
+        assert_eq!(Some(Gray8::new(12)), canvas.storage().pixels()[3]);
+        assert_eq!(Some(Gray8::new(18)), canvas.storage().pixels()[7]);
+        assert_eq!(None, canvas.storage().pixels()[11]);
+
+        let mut canvas = Canvas::new(Size::new(4, 5));
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(-1, -1), Size::new(10, 10)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+        assert!(canvas
+            .storage()
+            .pixels()
+            .iter()
+            .all(|pixel| pixel.is_some()));
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(2, 2)),
+                Gray8::BLACK,
+            )
+            .unwrap();
+        assert_eq!(
+            [
+                Some(Gray8::WHITE),
+                Some(Gray8::BLACK),
+                Some(Gray8::BLACK),
+                Some(Gray8::WHITE)
+            ],
+            canvas.storage().pixels()[4..8]
+        );
+    }
 
-            assert_eq!(
-                center,
-                canvas
-                    .index_to_point(center_index)
-                    .expect("Should fetch the index")
-            );
+    #[test]
+    fn test_draw_spans() {
+        use alloc::vec::Vec;
+        use embedded_graphics_core::pixelcolor::BinaryColor;
+
+        /// Records the areas of `fill_contiguous` and the pixels of `draw_iter`
+        #[derive(Default)]
+        struct Recorder {
+            spans: Vec<Rectangle>,
+            pixels: usize,
         }
-        {
-            let bottom_right = Point::new(320 - 1, 240 - 1);
-            let br_index = canvas
-                .point_to_index(bottom_right)
-                .expect("Inside the canvas");
-
-            assert_eq!(
-                bottom_right,
-                canvas
-                    .index_to_point(br_index)
-                    .expect("Should fetch the index")
-            );
+
+        impl OriginDimensions for Recorder {
+            fn size(&self) -> Size {
+                Size::new(20, 20)
+            }
         }
-        {
-            let top_left = Point::new(0, 0);
-            let tl_index = canvas.point_to_index(top_left).expect("Inside the canvas");
-
-            assert_eq!(
-                top_left,
-                canvas
-                    .index_to_point(tl_index)
-                    .expect("Should fetch the index")
-            );
+
+        impl DrawTarget for Recorder {
+            type Color = BinaryColor;
+            type Error = core::convert::Infallible;
+
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Pixel<Self::Color>>,
+            {
+                self.pixels += pixels.into_iter().count();
+
+                Ok(())
+            }
+
+            fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Self::Color>,
+            {
+                assert_eq!(
+                    area.size.width * area.size.height,
+                    colors.into_iter().count() as u32
+                );
+                self.spans.push(*area);
+
+                Ok(())
+            }
         }
 
-        {
-            let bottom_left = Point::new(0, 240 - 1);
-            let bl_index = canvas
-                .point_to_index(bottom_left)
-                .expect("Inside the canvas");
-
-            assert_eq!(
-                bottom_left,
-                canvas
-                    .index_to_point(bl_index)
-                    .expect("Should fetch the index")
-            );
+        let mut canvas = CanvasAt::new(Point::new(1, 1), Size::new(8, 4));
+        // 2 fully drawn rows
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(8, 2)),
+                BinaryColor::On,
+            )
+            .unwrap();
+        // a row with a narrow run and a wide run
+        canvas
+            .draw_iter([
+                Pixel(Point::new(1, 3), BinaryColor::On),
+                Pixel(Point::new(2, 3), BinaryColor::On),
+            ])
+            .unwrap();
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 3), Size::new(5, 1)),
+                BinaryColor::Off,
+            )
+            .unwrap();
+        // the last row is fully drawn again
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 4), Size::new(8, 1)),
+                BinaryColor::Off,
+            )
+            .unwrap();
+
+        let mut recorder = Recorder::default();
+        canvas.draw(&mut recorder).unwrap();
+
+        // the narrow run is drawn pixel by pixel
+        assert_eq!(2, recorder.pixels);
+        assert_eq!(
+            [
+                Rectangle::new(Point::new(4, 3), Size::new(5, 1)),
+                Rectangle::new(Point::new(1, 1), Size::new(8, 2)),
+                Rectangle::new(Point::new(1, 4), Size::new(8, 1)),
+            ],
+            recorder.spans[..]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-graphics")]
+    fn test_image_drawable() {
+        use embedded_graphics::image::{Image, ImageDrawableExt};
+
+        let mut canvas = Canvas::new(Size::new(4, 4));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(1, 1), BinaryColor::On),
+                Pixel(Point::new(2, 2), BinaryColor::Off),
+                Pixel(Point::new(3, 3), BinaryColor::On),
+            ])
+            .unwrap();
+
+        let mut display = CanvasAt::new(Point::zero(), Size::new(10, 10));
+        Image::new(&canvas, Point::new(5, 5))
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(Some(BinaryColor::On), display.get_pixel(Point::new(6, 6)));
+        assert_eq!(Some(BinaryColor::Off), display.get_pixel(Point::new(7, 7)));
+        assert_eq!(Some(BinaryColor::On), display.get_pixel(Point::new(8, 8)));
+        // transparent pixels are skipped
+        assert_eq!(3, display.storage().pixels().iter().flatten().count());
+
+        // the sub image area is partially outside of the canvas
+        let sub_image = canvas.sub_image(&Rectangle::new(Point::new(2, 2), Size::new(4, 4)));
+        let mut display = CanvasAt::new(Point::zero(), Size::new(10, 10));
+        Image::new(&sub_image, Point::new(1, 0))
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(Some(BinaryColor::Off), display.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(BinaryColor::On), display.get_pixel(Point::new(2, 1)));
+        assert_eq!(2, display.storage().pixels().iter().flatten().count());
+    }
+
+    #[test]
+    fn test_get_pixel() {
+        fn pixels<I: GetPixel>(image: &I, points: &[Point]) -> Vec<Option<I::Color>> {
+            points.iter().map(|point| image.pixel(*point)).collect()
         }
-        {
-            let top_right = Point::new(320 - 1, 0);
-            let tr_index = canvas.point_to_index(top_right).expect("Inside the canvas");
-
-            assert_eq!(
-                top_right,
-                canvas
-                    .index_to_point(tr_index)
-                    .expect("Should fetch the index")
-            );
+
+        let mut canvas = Canvas::new(Size::new(3, 3));
+        Pixel(Point::new(1, 2), BinaryColor::On)
+            .draw(&mut canvas)
+            .unwrap();
+        let points = [Point::new(1, 2), Point::new(0, 0), Point::new(3, 0)];
+        assert_eq!(
+            [Some(BinaryColor::On), None, None],
+            pixels(&canvas, &points)[..]
+        );
+
+        let canvas_at = canvas.place_at(Point::new(-1, 0));
+        let points = [Point::new(0, 2), Point::new(1, 2), Point::new(-2, 2)];
+        assert_eq!(
+            [Some(BinaryColor::On), None, None],
+            pixels(&canvas_at, &points)[..]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        // 3x2 canvas
+        // O.X
+        // ...
+        let mut canvas = Canvas::new(Size::new(3, 2));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 0), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let rotated = canvas.rotate_90();
+        assert_eq!(Size::new(2, 3), rotated.size());
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(1, 2)));
+        assert_eq!(2, rotated.storage().pixels().iter().flatten().count());
+
+        let rotated = canvas.rotate_270();
+        assert_eq!(Size::new(2, 3), rotated.size());
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(0, 2)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(0, 0)));
+
+        let rotated = canvas.rotate_180();
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(2, 1)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(0, 1)));
+
+        let mut in_place = canvas.clone();
+        in_place.rotate_180_in_place();
+        assert_eq!(rotated.storage().pixels(), in_place.storage().pixels());
+
+        let mut canvas_at = canvas.place_at(Point::new(5, 5));
+        canvas_at.rotate_90_in_place();
+        canvas_at.rotate_270_in_place();
+        assert_eq!(canvas.storage().pixels(), canvas_at.storage().pixels());
+        assert_eq!(Point::new(5, 5), canvas_at.top_left);
+        assert_eq!(
+            Some(BinaryColor::On),
+            canvas_at.rotate_90().get_pixel(Point::new(6, 5))
+        );
+    }
+
+    #[test]
+    fn test_flip() {
+        // 3x3 canvas
+        // O..
+        // ...
+        // .XX
+        let mut canvas = Canvas::new(Size::new(3, 3));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 2), BinaryColor::Off),
+                Pixel(Point::new(2, 2), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let flipped = canvas.flip_horizontal();
+        assert_eq!(Some(BinaryColor::On), flipped.get_pixel(Point::new(2, 0)));
+        assert_eq!(Some(BinaryColor::Off), flipped.get_pixel(Point::new(0, 2)));
+        assert_eq!(Some(BinaryColor::Off), flipped.get_pixel(Point::new(1, 2)));
+        assert_eq!(3, flipped.storage().pixels().iter().flatten().count());
+
+        let flipped = canvas.flip_vertical();
+        assert_eq!(Some(BinaryColor::On), flipped.get_pixel(Point::new(0, 2)));
+        assert_eq!(Some(BinaryColor::Off), flipped.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(BinaryColor::Off), flipped.get_pixel(Point::new(2, 0)));
+        assert_eq!(3, flipped.storage().pixels().iter().flatten().count());
+
+        let mut flipped = canvas.flip_horizontal();
+        flipped.flip_vertical_in_place();
+        assert_eq!(
+            canvas.rotate_180().storage().pixels(),
+            flipped.storage().pixels()
+        );
+
+        // flipped around its own bounding box
+        let mut canvas_at = canvas.place_at(Point::new(10, 20));
+        Pixel(Point::new(10, 20), BinaryColor::On)
+            .draw(&mut canvas_at)
+            .unwrap();
+        canvas_at.flip_horizontal_in_place();
+        canvas_at.flip_vertical_in_place();
+        assert_eq!(
+            Some(BinaryColor::Off),
+            canvas_at.get_pixel(Point::new(11, 20))
+        );
+        assert_eq!(
+            Some(BinaryColor::On),
+            canvas_at.get_pixel(Point::new(12, 22))
+        );
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut canvas = Canvas::new(Size::new(2, 2));
+        Pixel(Point::new(1, 0), BinaryColor::On)
+            .draw(&mut canvas)
+            .unwrap();
+
+        let scaled = canvas.scale_nearest(Size::new(4, 4));
+        assert_eq!(Size::new(4, 4), scaled.size());
+        assert_eq!(4, scaled.storage().pixels().iter().flatten().count());
+        for point in [(2, 0), (3, 0), (2, 1), (3, 1)] {
+            assert_eq!(Some(BinaryColor::On), scaled.get_pixel(point.into()));
         }
+        assert_eq!(
+            scaled.storage().pixels(),
+            canvas
+                .scale_to(Size::new(4, 4), Filter::Nearest)
+                .storage()
+                .pixels()
+        );
+
+        let scaled = canvas.scale_to(Size::new(1, 1), Filter::Bilinear);
+        assert_eq!(None, scaled.get_pixel(Point::zero()));
+    }
+
+    #[test]
+    fn test_transform_affine() {
+        let mut canvas = Canvas::new(Size::new(3, 2));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        // same as `rotate_90` but placed left of the origin
+        let rotated = canvas.transform_affine(&Affine::rotation(90)).unwrap();
+        assert_eq!(
+            Rectangle::new(Point::new(-2, 0), Size::new(2, 3)),
+            rotated.bounding_box()
+        );
+        assert_eq!(
+            canvas.rotate_90().storage().pixels(),
+            rotated.storage().pixels()
+        );
+
+        let scaled = canvas
+            .place_at(Point::new(10, 10))
+            .transform_affine(&Affine::scale(Affine::ONE * 2, Affine::ONE))
+            .unwrap();
+        assert_eq!(
+            Rectangle::new(Point::new(10, 10), Size::new(6, 2)),
+            scaled.bounding_box()
+        );
+        assert_eq!(Some(BinaryColor::On), scaled.get_pixel(Point::new(11, 10)));
+        assert_eq!(Some(BinaryColor::Off), scaled.get_pixel(Point::new(14, 11)));
+        assert_eq!(4, scaled.storage().pixels().iter().flatten().count());
+
+        assert!(canvas.transform_affine(&Affine::scale(0, 0)).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "transform")]
+    fn test_translate() {
+        use embedded_graphics::transform::Transform;
+
+        let mut canvas = Canvas::new(Size::new(3, 3));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 2), BinaryColor::Off),
+            ])
+            .unwrap();
+        Pixel(Point::new(1, 1), BinaryColor::On)
+            .draw(&mut canvas)
+            .unwrap();
+
+        let translated = canvas.translate(Point::new(1, 0));
+        assert_eq!(Size::new(3, 3), translated.size());
+        assert_eq!(
+            Some(BinaryColor::On),
+            translated.get_pixel(Point::new(1, 0))
+        );
+        assert_eq!(
+            Some(BinaryColor::On),
+            translated.get_pixel(Point::new(2, 1))
+        );
+        // translated outside of the canvas
+        assert_eq!(2, translated.storage().pixels().iter().flatten().count());
+
+        canvas.translate_mut(Point::new(1, 0));
+        assert_eq!(translated.storage().pixels(), canvas.storage().pixels());
+    }
+
+    #[test]
+    fn test_trim() {
+        let canvas = Canvas::<BinaryColor>::new(Size::new(10, 10));
+        assert_eq!(None, canvas.content_bounding_box());
+        assert!(canvas.trim().is_none());
+
+        let mut canvas_at = canvas.place_at(Point::new(-5, 5));
+        canvas_at
+            .draw_iter([
+                Pixel(Point::new(-3, 6), BinaryColor::On),
+                Pixel(Point::new(0, 9), BinaryColor::Off),
+            ])
+            .unwrap();
+        let bounding_box = Rectangle::new(Point::new(-3, 6), Size::new(4, 4));
+        assert_eq!(Some(bounding_box), canvas_at.content_bounding_box());
+
+        let trimmed = canvas_at.trim().unwrap();
+        assert_eq!(bounding_box, trimmed.bounding_box());
+        assert_eq!(Some(BinaryColor::On), trimmed.get_pixel(Point::new(-3, 6)));
+        assert_eq!(Some(BinaryColor::Off), trimmed.get_pixel(Point::new(0, 9)));
+    }
+
+    #[test]
+    fn test_resize_and_pad() {
+        let mut canvas = Canvas::new(Size::new(2, 2));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let resized = canvas.resize(Size::new(4, 4), AnchorPoint::Center);
+        assert_eq!(Size::new(4, 4), resized.size());
+        assert_eq!(Some(BinaryColor::On), resized.get_pixel(Point::new(1, 1)));
+        assert_eq!(Some(BinaryColor::Off), resized.get_pixel(Point::new(2, 2)));
+        assert_eq!(2, resized.storage().pixels().iter().flatten().count());
+
+        // shrinking drops the pixels outside of the new size
+        let resized = canvas.resize(Size::new(1, 1), AnchorPoint::BottomRight);
+        assert_eq!(Some(BinaryColor::Off), resized.get_pixel(Point::zero()));
+
+        // the transparent pixels of the canvas stay transparent
+        let padded = canvas.pad_with_color(1, 0, 0, 2, BinaryColor::On).unwrap();
+        assert_eq!(Size::new(4, 3), padded.size());
+        assert_eq!(None, padded.get_pixel(Point::new(3, 1)));
+        assert_eq!(Some(BinaryColor::Off), padded.get_pixel(Point::new(3, 2)));
+        assert_eq!(Some(BinaryColor::On), padded.get_pixel(Point::new(0, 0)));
+        assert_eq!(10, padded.storage().pixels().iter().flatten().count());
+
+        let canvas_at = canvas.place_at(Point::new(5, 5)).pad(1, 1, 1, 1).unwrap();
+        assert_eq!(
+            Rectangle::new(Point::new(4, 4), Size::new(4, 4)),
+            canvas_at.bounding_box()
+        );
+        assert_eq!(Some(BinaryColor::On), canvas_at.get_pixel(Point::new(5, 5)));
+        assert_eq!(
+            Some(BinaryColor::On),
+            canvas_at
+                .resize_with_color(Size::new(5, 5), AnchorPoint::TopLeft, BinaryColor::On)
+                .get_pixel(Point::new(8, 8))
+        );
+
+        // the padded size doesn't fit
+        assert!(canvas.pad(0, u32::MAX, 0, 1).is_none());
+        assert!(canvas_at.pad(u32::MAX / 2 + 1, 0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut canvas = Canvas::new(Size::new(3, 2));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let mut wrapped = canvas.clone();
+        wrapped.scroll(1, 1, Wrap::Yes);
+        assert_eq!(Some(BinaryColor::On), wrapped.get_pixel(Point::new(1, 1)));
+        assert_eq!(Some(BinaryColor::Off), wrapped.get_pixel(Point::new(0, 0)));
+        assert_eq!(2, wrapped.storage().pixels().iter().flatten().count());
+
+        let mut canvas_at = canvas.place_at(Point::new(10, 10));
+        canvas_at.scroll(-2, 0, Wrap::No);
+        assert_eq!(
+            Some(BinaryColor::Off),
+            canvas_at.get_pixel(Point::new(10, 11))
+        );
+        assert_eq!(1, canvas_at.storage().pixels().iter().flatten().count());
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut source = Canvas::new(Size::new(3, 3));
+        source
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 1), BinaryColor::Off),
+                Pixel(Point::new(2, 2), BinaryColor::On),
+            ])
+            .unwrap();
+
+        let mut canvas = Canvas::with_default_color(Size::new(4, 4), BinaryColor::Off);
+        // the source area and the destination are clipped
+        canvas.blit(
+            &source,
+            &Rectangle::new(Point::new(-1, 0), Size::new(4, 3)),
+            Point::new(2, 2),
+        );
+        assert_eq!(Some(BinaryColor::On), canvas.get_pixel(Point::new(3, 2)));
+        // transparent source pixels are skipped
+        assert_eq!(Some(BinaryColor::Off), canvas.get_pixel(Point::new(2, 2)));
+        assert_eq!(Some(BinaryColor::Off), canvas.get_pixel(Point::new(3, 3)));
+
+        let mut canvas_at = CanvasAt::new(Point::new(-10, -10), Size::new(4, 4));
+        canvas_at.blit(&source, &source.bounding_box(), Point::new(-9, -9));
+        assert_eq!(
+            Some(BinaryColor::On),
+            canvas_at.get_pixel(Point::new(-9, -9))
+        );
+        assert_eq!(
+            Some(BinaryColor::On),
+            canvas_at.get_pixel(Point::new(-7, -7))
+        );
+        assert_eq!(3, canvas_at.storage().pixels().iter().flatten().count());
     }
 }
@@ -0,0 +1,335 @@
+//! Nested clip areas for drawing on a canvas.
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+/// Error returned when pushing a clip area on a [`ClipStack`] which is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipDepthError;
+
+impl core::fmt::Display for ClipDepthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The clip stack is full")
+    }
+}
+
+/// A [`DrawTarget`] adapter which borrows a target and restricts drawing on it
+/// to a stack of up to `N` nested clip areas.
+///
+/// Every pushed clip area is intersected with the current one and drawing outside
+/// of the current clip area is skipped. Without clip areas drawing is passed to the
+/// target as is. The clip areas use the coordinates of the target,
+/// i.e. the **display** coordinates for the canvases placed on the display.
+///
+/// The clip areas are dropped with the [`ClipStack`], the target itself is never clipped.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::CCanvas;
+/// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+///
+/// let mut canvas = CCanvas::<Rgb565, 64, 64>::new();
+///
+/// let mut clipped = canvas.clipped_stack::<2>();
+/// clipped
+///     .push(&Rectangle::new(Point::new(8, 8), Size::new(32, 32)))
+///     .unwrap();
+/// clipped
+///     .push(&Rectangle::new(Point::new(24, 24), Size::new(32, 32)))
+///     .unwrap();
+/// assert_eq!(
+///     Some(Rectangle::new(Point::new(24, 24), Size::new(16, 16))),
+///     clipped.clip_area()
+/// );
+///
+/// // only the 16x16 pixels of the clip area are drawn
+/// clipped.clear(Rgb565::RED)?;
+/// assert_eq!(None, clipped.get_ref().get_pixel(Point::new(8, 8)));
+/// assert_eq!(Some(Rgb565::RED), clipped.get_ref().get_pixel(Point::new(24, 24)));
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+#[derive(Debug)]
+pub struct ClipStack<'a, T, const N: usize> {
+    target: &'a mut T,
+    areas: [Rectangle; N],
+    len: usize,
+}
+
+impl<'a, T, const N: usize> ClipStack<'a, T, N> {
+    /// Create a new [`ClipStack`] without clip areas for the `target`.
+    pub fn new(target: &'a mut T) -> Self {
+        Self {
+            target,
+            areas: [Rectangle::zero(); N],
+            len: 0,
+        }
+    }
+
+    /// Pushes a clip area intersected with the current clip area, after which drawing
+    /// is restricted to it until it's popped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipDepthError`] if there are already `N` clip areas.
+    pub fn push(&mut self, area: &Rectangle) -> Result<(), ClipDepthError> {
+        let area = match self.clip_area() {
+            Some(current) => current.intersection(area),
+            None => *area,
+        };
+        let slot = self.areas.get_mut(self.len).ok_or(ClipDepthError)?;
+        *slot = area;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Pops the last pushed clip area and restores the previous one.
+    ///
+    /// Returns the popped clip area or [`None`] if there are no clip areas.
+    pub fn pop(&mut self) -> Option<Rectangle> {
+        self.len = self.len.checked_sub(1)?;
+
+        Some(self.areas[self.len])
+    }
+
+    /// Returns the current clip area, i.e. the intersection of the pushed clip areas.
+    ///
+    /// Returns [`None`] if there are no clip areas.
+    pub fn clip_area(&self) -> Option<Rectangle> {
+        self.len.checked_sub(1).map(|last| self.areas[last])
+    }
+
+    /// Returns the target.
+    pub fn get_ref(&self) -> &T {
+        self.target
+    }
+
+    /// Returns the target for modifying it directly, without the clip areas.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.target
+    }
+}
+
+impl<T: Dimensions, const N: usize> Dimensions for ClipStack<'_, T, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<T: DrawTarget, const N: usize> DrawTarget for ClipStack<'_, T, N> {
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        match self.clip_area() {
+            Some(clip) => self.target.draw_iter(
+                pixels
+                    .into_iter()
+                    .filter(|Pixel(point, _color)| clip.contains(*point)),
+            ),
+            None => self.target.draw_iter(pixels),
+        }
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let Some(clip) = self.clip_area() else {
+            return self.target.fill_contiguous(area, colors);
+        };
+
+        let clipped = area.intersection(&clip);
+        if clipped == *area {
+            return self.target.fill_contiguous(area, colors);
+        }
+
+        // the colors of the whole area are always consumed,
+        // so an iterator shared with the caller stays aligned
+        let pixel_count = area.size.width as usize * area.size.height as usize;
+        let mut colors = colors.into_iter().take(pixel_count);
+
+        if !clipped.is_zero_sized() {
+            // only the colors of the clipped area are passed to the target
+            let clipped_colors = area
+                .points()
+                .zip(&mut colors)
+                .filter(|(point, _color)| clipped.contains(*point))
+                .map(|(_point, color)| color);
+
+            self.target.fill_contiguous(&clipped, clipped_colors)?;
+        }
+        colors.for_each(drop);
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match self.clip_area() {
+            Some(clip) => self.target.fill_solid(&area.intersection(&clip), color),
+            None => self.target.fill_solid(area, color),
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        match self.clip_area() {
+            Some(clip) => self.target.fill_solid(&clip, color),
+            None => self.target.clear(color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use super::*;
+    use crate::{CCanvas, CCanvasAt};
+
+    #[test]
+    fn test_clip_stack_depth() {
+        let mut canvas = CCanvas::<Gray8, 4, 4>::new();
+        let mut clipped = canvas.clipped_stack::<2>();
+        assert_eq!(None, clipped.clip_area());
+
+        let area = Rectangle::new(Point::zero(), Size::new(4, 4));
+        clipped.push(&area).unwrap();
+        clipped.push(&area).unwrap();
+        assert_eq!(Err(ClipDepthError), clipped.push(&area));
+
+        assert_eq!(Some(area), clipped.pop());
+        assert_eq!(Some(area), clipped.pop());
+        assert_eq!(None, clipped.pop());
+    }
+
+    #[test]
+    fn test_clip_stack_without_intersection() {
+        let mut canvas = CCanvasAt::<Gray8, 4, 4>::new(Point::new(10, 10));
+        let mut clipped = canvas.clipped_stack::<2>();
+        clipped
+            .push(&Rectangle::new(Point::new(10, 10), Size::new(2, 2)))
+            .unwrap();
+        // the nested clip area doesn't intersect, so nothing is drawn
+        clipped
+            .push(&Rectangle::new(Point::new(12, 12), Size::new(2, 2)))
+            .unwrap();
+        assert!(clipped.clip_area().unwrap().is_zero_sized());
+
+        clipped.clear(Gray8::WHITE).unwrap();
+        clipped
+            .fill_contiguous(
+                &Rectangle::new(Point::new(10, 10), Size::new(4, 4)),
+                (0..16).map(Gray8::new),
+            )
+            .unwrap();
+        Pixel(Point::new(12, 12), Gray8::WHITE)
+            .draw(&mut clipped)
+            .unwrap();
+        assert_eq!(None, clipped.get_ref().get_pixel(Point::new(10, 10)));
+        assert_eq!(None, clipped.get_ref().get_pixel(Point::new(12, 12)));
+
+        // the outer clip area is restored after popping the empty one
+        clipped.pop();
+        clipped.clear(Gray8::WHITE).unwrap();
+        assert_eq!(
+            Some(Gray8::WHITE),
+            clipped.get_ref().get_pixel(Point::new(11, 11))
+        );
+        assert_eq!(None, clipped.get_ref().get_pixel(Point::new(12, 12)));
+    }
+
+    #[test]
+    fn test_clip_stack_fill_contiguous() {
+        let mut canvas = CCanvasAt::<Gray8, 4, 4>::new(Point::new(10, 10));
+        let mut clipped = canvas.clipped_stack::<1>();
+        // the clip area sticks out of the canvas on the left
+        clipped
+            .push(&Rectangle::new(Point::new(8, 11), Size::new(4, 2)))
+            .unwrap();
+
+        clipped
+            .fill_contiguous(
+                &Rectangle::new(Point::new(10, 10), Size::new(4, 4)),
+                (0..16).map(Gray8::new),
+            )
+            .unwrap();
+
+        // the colors stay aligned with the points of the unclipped area
+        assert_eq!(Some(Gray8::new(4)), canvas.get_pixel(Point::new(10, 11)));
+        assert_eq!(Some(Gray8::new(9)), canvas.get_pixel(Point::new(11, 12)));
+        assert_eq!(None, canvas.get_pixel(Point::new(10, 10)));
+        assert_eq!(None, canvas.get_pixel(Point::new(12, 11)));
+        assert_eq!(None, canvas.get_pixel(Point::new(10, 13)));
+    }
+
+    #[test]
+    fn test_clip_stack_consumes_colors() {
+        let mut canvas = CCanvas::<Gray8, 4, 4>::new();
+        let mut clipped = canvas.clipped_stack::<1>();
+        clipped
+            .push(&Rectangle::new(Point::new(2, 0), Size::new(2, 4)))
+            .unwrap();
+
+        let mut colors = (0..16).map(Gray8::new);
+        // outside of the clip area
+        clipped
+            .fill_contiguous(
+                &Rectangle::new(Point::zero(), Size::new(2, 2)),
+                colors.by_ref(),
+            )
+            .unwrap();
+        assert_eq!(Some(Gray8::new(4)), colors.next());
+
+        // the last pixel is outside of the clip area
+        clipped
+            .fill_contiguous(
+                &Rectangle::new(Point::new(2, 1), Size::new(3, 1)),
+                colors.by_ref(),
+            )
+            .unwrap();
+        assert_eq!(Some(Gray8::new(8)), colors.next());
+        assert_eq!(Some(Gray8::new(5)), canvas.get_pixel(Point::new(2, 1)));
+        assert_eq!(Some(Gray8::new(6)), canvas.get_pixel(Point::new(3, 1)));
+    }
+
+    #[test]
+    fn test_clip_stack_popped_before_place_at() {
+        let mut canvas = CCanvas::<Gray8, 4, 4>::new();
+        {
+            let mut clipped = canvas.clipped_stack::<1>();
+            clipped
+                .push(&Rectangle::new(Point::zero(), Size::new(1, 1)))
+                .unwrap();
+            clipped.clear(Gray8::WHITE).unwrap();
+        }
+
+        // the clip area of the canvas coordinates doesn't follow the canvas,
+        // drawing on the placed canvas isn't clipped
+        let mut canvas = canvas.place_at(Point::new(10, 10));
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(12, 12), Size::new(2, 2)),
+                Gray8::BLACK,
+            )
+            .unwrap();
+        assert_eq!(Some(Gray8::WHITE), canvas.get_pixel(Point::new(10, 10)));
+        assert_eq!(Some(Gray8::BLACK), canvas.get_pixel(Point::new(13, 13)));
+
+        // a new clip area uses the display coordinates of the placed canvas
+        let mut clipped = canvas.clipped_stack::<1>();
+        clipped
+            .push(&Rectangle::new(Point::new(0, 0), Size::new(11, 11)))
+            .unwrap();
+        clipped.clear(Gray8::new(128)).unwrap();
+        assert_eq!(
+            Some(Gray8::new(128)),
+            clipped.get_ref().get_pixel(Point::new(10, 10))
+        );
+        assert_eq!(
+            Some(Gray8::BLACK),
+            clipped.get_ref().get_pixel(Point::new(13, 13))
+        );
+    }
+}
@@ -1,30 +1,30 @@
 //! `Canvas`es implemented with const generics
 use embedded_graphics_core::{
-    prelude::{
-        Dimensions, DrawTarget, Drawable, OriginDimensions, Pixel, PixelColor, Point, PointsIter,
-        Size,
-    },
+    prelude::{PixelColor, Point, Size},
     primitives::Rectangle,
 };
 
-use crate::utils::center_offset;
+use crate::{
+    affine::Affine,
+    canvas::{rotate_into, transform_affine, GenericCanvas, GenericCanvasAt},
+    utils::{center_offset, Rotation},
+};
 
 /// Canvas on which you can draw but it's not drawable on the display yet.
 /// Implemented using [const generics][const_generics_rfc].
 ///
 /// Draw on the [`CCanvas`] using origin of [`Point::zero()`].
 ///
+/// The pixels are stored column by column in an array,
+/// see [`GenericCanvas`] for all the methods of the canvas.
+///
 /// The width (`W`) and height (`H`) constants of the [`CCanvas`]
 /// should less than [`u32::MAX`] as [`Size`] uses [`u32`].
 ///
 /// [const_generics_rfc]: https://rust-lang.github.io/rfcs/2000-const-generics.html
-pub struct CCanvas<C, const W: usize, const H: usize> {
-    // we also store the size for working with embedded-graphics
-    pub size: Size,
-    pub pixels: [[Option<C>; H]; W],
-}
+pub type CCanvas<C, const W: usize, const H: usize> = GenericCanvas<[[Option<C>; H]; W]>;
 
-impl<C: Copy + PartialEq, const W: usize, const H: usize> Default for CCanvas<C, W, H> {
+impl<C: PixelColor, const W: usize, const H: usize> Default for CCanvas<C, W, H> {
     fn default() -> Self {
         Self::new()
     }
@@ -32,7 +32,7 @@ impl<C: Copy + PartialEq, const W: usize, const H: usize> Default for CCanvas<C,
 
 impl<C, const W: usize, const H: usize> CCanvas<C, W, H>
 where
-    C: Copy + PartialEq,
+    C: PixelColor,
 {
     /// Create a new blank [`CCanvas`].
     ///
@@ -40,11 +40,10 @@ where
     ///
     /// If either the width (`W`) or heigh (`H`) is larger than [`u32::MAX`]
     /// due to the internal [`Size`] used for implementing [`OriginDimensions`].
+    ///
+    /// [`OriginDimensions`]: embedded_graphics_core::prelude::OriginDimensions
     pub fn new() -> Self {
-        Self {
-            size: Size::new(W as u32, H as u32),
-            pixels: [[None; H]; W],
-        }
+        GenericCanvas::from_storage([[None; H]; W])
     }
 
     /// Create a [`CCanvas`] filled with a default color.
@@ -53,136 +52,115 @@ where
     ///
     /// If either the width (`W`) or heigh (`H`) is larger than [`u32::MAX`]
     /// due to the internal [`Size`] used for implementing [`OriginDimensions`].
-    pub fn with_default_color(default_color: C) -> Self {
-        Self {
-            size: Size::new(W as u32, H as u32),
-            pixels: [[Some(default_color); H]; W],
-        }
-    }
-
-    /// Returns the color of the pixel at a given [`Point`].
     ///
-    /// Returns [`None`] if the [`Point`] is outside of the [`CCanvas`].
-    pub fn get_pixel(&self, point: Point) -> Option<C> {
-        // `Point` implements TryFrom only for `u32` & `i32`.
-        let x = usize::try_from(point.x).ok()?;
-        let y = usize::try_from(point.y).ok()?;
-
-        self.pixels
-            .get(x)
-            .and_then(|x_row| x_row.get(y))
-            .copied()
-            .flatten()
-    }
-
-    /// Returns the center of [`Size`] of the [`CCanvas`].
-    pub fn center(&self) -> Point {
-        Point::zero() + center_offset(self.size)
+    /// [`OriginDimensions`]: embedded_graphics_core::prelude::OriginDimensions
+    pub fn with_default_color(default_color: C) -> Self {
+        GenericCanvas::from_storage([[Some(default_color); H]; W])
     }
-}
 
-impl<C, const W: usize, const H: usize> CCanvas<C, W, H>
-where
-    C: PixelColor,
-{
     /// Create a new cropped [`CCanvas`].
     ///
     /// This method takes into account the top left [`Point`] of the `area`
     /// you'd like to crop relative to the [`CCanvas`] itself.
-    //
+    ///
+    /// Pixels of the `area` which do not fit in the new width (`NW`)
+    /// and height (`NH`) are not copied.
+    ///
     /// If the width or height of the [`Rectangle`] is `0`, this method will
     /// return [`None`] (see [`Rectangle::bottom_right()`])
-    // todo: make safer
     pub fn crop<const NW: usize, const NH: usize>(
         &self,
         area: &Rectangle,
     ) -> Option<CCanvas<C, NW, NH>> {
-        let mut new = CCanvas::<C, NW, NH>::new();
-
         // returns None when width or height is `0`
-        // it's safe to return `None` for Canvas too!
-        let area_bottom_right = area.bottom_right()?;
-
-        let new_pixels = self.pixels.iter().enumerate().flat_map(|(x, x_row)| {
-            x_row.iter().enumerate().filter_map(move |(y, color)| {
-                let color = match color {
-                    Some(color) => *color,
-                    None => return None,
-                };
-
-                // Canvas always starts from `Point::zero()`
-                // todo: make safer
-                let point = Point::new(x as i32, y as i32);
-
-                // for here on, we should compare the point based on the area we want to crop
-                if point >= area.top_left && point <= area_bottom_right {
-                    // remove the area top_left to make the origin at `Point::zero()` for the cropped part
-                    let pixel = Pixel(point - area.top_left, color);
-
-                    Some(pixel)
-                } else {
-                    None
-                }
-            })
-        });
-
-        new.draw_iter(new_pixels).ok().map(|_| new)
+        area.bottom_right()?;
+
+        let mut new = CCanvas::<C, NW, NH>::new();
+        self.crop_into(area, &mut new).ok().map(|_| new)
     }
 
-    /// Sets the place with top left offset where the canvas will be drawn to the display.
-    pub fn place_at(&self, top_left: Point) -> CCanvasAt<C, W, H> {
-        CCanvasAt {
-            top_left,
-            size: self.size,
-            pixels: self.pixels,
-        }
+    /// Returns a new [`CCanvas`] rotated by 90 degrees clockwise.
+    ///
+    /// The width (`W`) and height (`H`) of the rotated [`CCanvas`] are swapped.
+    pub fn rotate_90(&self) -> CCanvas<C, H, W> {
+        GenericCanvas::from_storage(rotate_pixels(self.storage(), Rotation::Deg90))
     }
 
-    /// Sets the center of the [`CCanvas`] where it will be drawn to the display.
-    pub fn place_center(&self, center: Point) -> CCanvasAt<C, W, H> {
-        let top_left = center - center_offset(self.size);
+    /// Returns a new [`CCanvas`] rotated by 270 degrees clockwise
+    /// (90 degrees counterclockwise).
+    ///
+    /// The width (`W`) and height (`H`) of the rotated [`CCanvas`] are swapped.
+    pub fn rotate_270(&self) -> CCanvas<C, H, W> {
+        GenericCanvas::from_storage(rotate_pixels(self.storage(), Rotation::Deg270))
+    }
 
-        self.place_at(top_left)
+    /// Returns a new [`CCanvasAt`] with the pixels of the [`CCanvas`] transformed
+    /// by the `affine` transformation (see [`Affine`]).
+    ///
+    /// The top left corner of the new canvas is the one of the bounding box
+    /// of the transformed [`CCanvas`] (see [`Affine::bounding_box`]) relative to the
+    /// origin of the [`CCanvas`]. Transformed pixels outside of the new width (`NW`)
+    /// and height (`NH`) are dropped. The pixels are resampled using the nearest pixel,
+    /// so transparent pixels stay transparent.
+    ///
+    /// Returns [`None`] if the transformation can't be inverted
+    /// or the transformed bounding box doesn't fit in the [`Point`] coordinates.
+    pub fn transform_affine<const NW: usize, const NH: usize>(
+        &self,
+        affine: &Affine,
+    ) -> Option<CCanvasAt<C, NW, NH>> {
+        transform_affine(affine, self.storage(), Point::zero(), |_| [[None; NH]; NW])
     }
 }
 
-impl<C, const W: usize, const H: usize> OriginDimensions for CCanvas<C, W, H> {
-    fn size(&self) -> Size {
-        self.size
+impl<C, const N: usize> CCanvas<C, N, N>
+where
+    C: PixelColor,
+{
+    /// Rotates the square [`CCanvas`] by 90 degrees clockwise in place.
+    pub fn rotate_90_in_place(&mut self) {
+        rotate_90_in_place(self.storage_mut());
+    }
+
+    /// Rotates the square [`CCanvas`] by 270 degrees clockwise
+    /// (90 degrees counterclockwise) in place.
+    pub fn rotate_270_in_place(&mut self) {
+        rotate_270_in_place(self.storage_mut());
     }
 }
 
-impl<C: PixelColor, const W: usize, const H: usize> DrawTarget for CCanvas<C, W, H> {
-    type Color = C;
-    type Error = core::convert::Infallible;
-
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-    where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
-    {
-        for Pixel(point, color) in pixels.into_iter() {
-            // if Pixel is outside of the canvas, skip it
-            if let (Some(x), Some(y)) =
-                (usize::try_from(point.x).ok(), usize::try_from(point.y).ok())
-            {
-                self.pixels[x][y] = Some(color);
-            }
-        }
+/// Error returned when cropping a canvas into a canvas with const generic size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CropError {
+    /// The width or height of the area is `0`.
+    EmptyArea,
+    /// The size of the area is different from the const generic size of the new canvas.
+    SizeMismatch {
+        /// The size of the new canvas, i.e. the width (`NW`) and height (`NH`) constants.
+        expected: Size,
+        /// The size of the area being cropped.
+        actual: Size,
+    },
+}
 
-        Ok(())
+impl core::fmt::Display for CropError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CropError::EmptyArea => f.write_str("The area to crop has a width or height of 0"),
+            CropError::SizeMismatch { expected, actual } => write!(
+                f,
+                "The area to crop ({}x{}) does not match the canvas size ({}x{})",
+                actual.width, actual.height, expected.width, expected.height
+            ),
+        }
     }
 }
 
 /// Canvas which is drawable at the provided [`Point`] (location) on the display.
-#[derive(Debug, Clone, Copy)]
-pub struct CCanvasAt<C: PixelColor, const W: usize, const H: usize> {
-    /// The top left offset where the [`CCanvasAt`] will be drawn to the display.
-    pub top_left: Point,
-    /// The size of the [`CCanvasAt`].
-    size: Size,
-    /// The pixels of the [`CCanvasAt`].
-    pub pixels: [[Option<C>; H]; W],
-}
+///
+/// The pixels are stored column by column in an array,
+/// see [`GenericCanvasAt`] for all the methods of the canvas.
+pub type CCanvasAt<C, const W: usize, const H: usize> = GenericCanvasAt<[[Option<C>; H]; W]>;
 
 impl<C, const W: usize, const H: usize> CCanvasAt<C, W, H>
 where
@@ -194,24 +172,20 @@ where
     ///
     /// If either of width (`W`) or heigh (`H`) is larger than [`u32::MAX`]
     /// due to the internal [`Size`] used for implementing [`Dimensions`].
+    ///
+    /// [`Dimensions`]: embedded_graphics_core::prelude::Dimensions
     pub fn new(top_left: Point) -> Self {
-        Self {
-            top_left,
-            size: Size::new(W as u32, H as u32),
-            pixels: [[None; H]; W],
-        }
+        GenericCanvasAt::from_storage(top_left, [[None; H]; W])
     }
 
     /// Create a [`CCanvasAt`] filled with a default color.
     ///
     /// If either of width (`W`) or heigh (`H`) is larger than [`u32::MAX`]
     /// due to the internal [`Size`] used for implementing [`Dimensions`].
+    ///
+    /// [`Dimensions`]: embedded_graphics_core::prelude::Dimensions
     pub fn with_default_color(top_left: Point, default_color: C) -> Self {
-        Self {
-            top_left,
-            size: Size::new(W as u32, H as u32),
-            pixels: [[Some(default_color); H]; W],
-        }
+        GenericCanvasAt::from_storage(top_left, [[Some(default_color); H]; W])
     }
 
     /// Create a new blank [`CCanvasAt`] with a set center on the display.
@@ -220,142 +194,399 @@ where
     ///
     /// If either of width (`W`) or heigh (`H`) is larger than [`u32::MAX`]
     /// due to the internal [`Size`] used for implementing [`Dimensions`].
+    ///
+    /// [`Dimensions`]: embedded_graphics_core::prelude::Dimensions
     pub fn with_center(center: Point) -> Self {
         let top_left = center - center_offset(Size::new(W as u32, H as u32));
 
         Self::new(top_left)
     }
 
-    /// Returns the center of the bounding box.
-    pub fn center(&self) -> Point {
-        self.bounding_box().center()
+    /// Create a new cropped [`CCanvasAt`].
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the **display**.
+    ///
+    /// The new [`CCanvasAt`] is placed at the top left [`Point`] of the `area`
+    /// and has the size of the new width (`NW`) and height (`NH`) constants.
+    /// Pixels of the `area` which do not fit in `NW` x `NH` are not copied,
+    /// use [`CCanvasAt::try_crop`] if you want to make sure the sizes match.
+    ///
+    /// If the width or height of the [`Rectangle`] is `0`, this method will
+    /// return [`None`] (see [`Rectangle::bottom_right()`])
+    pub fn crop<const NW: usize, const NH: usize>(
+        &self,
+        area: &Rectangle,
+    ) -> Option<CCanvasAt<C, NW, NH>> {
+        // returns None when width or height is `0`
+        area.bottom_right()?;
+
+        let mut new = CCanvasAt::<C, NW, NH>::new(area.top_left);
+        self.crop_into(area, &mut new).ok().map(|_| new)
     }
 
-    /// Returns the color of the pixel at a given [`Point`].
+    /// Create a new cropped [`CCanvasAt`] making sure that the size of the `area`
+    /// is the same as the new width (`NW`) and height (`NH`) constants.
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the **display**.
     ///
-    /// Returns [`None`] if the [`Point`] is outside of the [`CCanvasAt`].
-    pub fn get_pixel(&self, point: Point) -> Option<C> {
-        // account for the top_left offset of the CanvasAt
-        let point_adjusted = point - self.top_left;
+    /// # Errors
+    ///
+    /// - [`CropError::EmptyArea`] when the width or height of the `area` is `0`
+    /// - [`CropError::SizeMismatch`] when the size of the `area` is not `NW` x `NH`
+    pub fn try_crop<const NW: usize, const NH: usize>(
+        &self,
+        area: &Rectangle,
+    ) -> Result<CCanvasAt<C, NW, NH>, CropError> {
+        if area.is_zero_sized() {
+            return Err(CropError::EmptyArea);
+        }
 
-        // `Point` implements TryFrom only for `u32` & `i32`.
-        let x = usize::try_from(point_adjusted.x).ok()?;
-        let y = usize::try_from(point_adjusted.y).ok()?;
+        let expected = Size::new(NW as u32, NH as u32);
 
-        self.pixels
-            .get(x)
-            .and_then(|x_row| x_row.get(y))
-            .copied()
-            .flatten()
+        if area.size != expected {
+            return Err(CropError::SizeMismatch {
+                expected,
+                actual: area.size,
+            });
+        }
+
+        self.crop(area).ok_or(CropError::EmptyArea)
     }
 
-    // /// Create a new cropped [`CCanvasAt`].
-    // ///
-    // /// This method takes into account the top left [`Point`] of the `area`
-    // /// you'd like to crop relative to the **display**.
-    // ///
-    // /// If the width or height of the [`Rectangle`] is `0`, this method will
-    // /// return [`None`] (see [`Rectangle::bottom_right()`])
-    // // todo: make safer
-    // pub fn crop(&self, area: &Rectangle) -> Option<CanvasAt<C>> {
-    //     let mut new = CanvasAt::new(area.top_left, area.size);
-
-    //     // returns None when width or height is `0`
-    //     // it's safe to return `None` for Canvas too!
-    //     let area_bottom_right = area.bottom_right()?;
-
-    //     let new_pixels = self.pixels.iter().enumerate().filter_map(|(index, color)| {
-    //         let color = match color {
-    //             Some(color) => *color,
-    //             None => return None,
-    //         };
-
-    //         let point = self.index_to_point(index).expect("Will never fail");
-
-    //         // for here on, we should compare the point based on the area we want to crop
-    //         if point >= area.top_left && point <= area_bottom_right {
-    //             let pixel = Pixel(point, color);
-
-    //             Some(pixel)
-    //         } else {
-    //             None
-    //         }
-    //     });
-
-    //     new.draw_iter(new_pixels).ok().map(|_| new)
-    // }
-}
+    /// Returns a new [`CCanvasAt`] rotated by 90 degrees clockwise.
+    ///
+    /// The width (`W`) and height (`H`) of the rotated [`CCanvasAt`] are swapped.
+    ///
+    /// The top left corner of the rotated [`CCanvasAt`] on the display stays the same.
+    pub fn rotate_90(&self) -> CCanvasAt<C, H, W> {
+        let rotated = rotate_pixels(self.storage(), Rotation::Deg90);
 
-impl<C: PixelColor, const W: usize, const H: usize> Dimensions for CCanvasAt<C, W, H> {
-    fn bounding_box(&self) -> Rectangle {
-        Rectangle::new(self.top_left, self.size)
+        GenericCanvasAt::from_storage(self.top_left, rotated)
     }
-}
 
-impl<C: PixelColor, const W: usize, const H: usize> DrawTarget for CCanvasAt<C, W, H> {
-    type Color = C;
-    type Error = core::convert::Infallible;
-
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-    where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
-    {
-        for Pixel(point, color) in pixels.into_iter() {
-            // account for the top_left offset of the CanvasAt
-            let point_adjusted = point - self.top_left;
-
-            // skip if:
-            // - Pixel's Point is outside of the canvas
-            // - Pixel's Point is not `usize` representable
-            if let (Some(x), Some(y)) = (
-                usize::try_from(point_adjusted.x).ok(),
-                usize::try_from(point_adjusted.y).ok(),
-            ) {
-                self.pixels[x][y] = Some(color);
-            };
-        }
+    /// Returns a new [`CCanvasAt`] rotated by 270 degrees clockwise
+    /// (90 degrees counterclockwise).
+    ///
+    /// The width (`W`) and height (`H`) of the rotated [`CCanvasAt`] are swapped.
+    ///
+    /// The top left corner of the rotated [`CCanvasAt`] on the display stays the same.
+    pub fn rotate_270(&self) -> CCanvasAt<C, H, W> {
+        let rotated = rotate_pixels(self.storage(), Rotation::Deg270);
 
-        Ok(())
+        GenericCanvasAt::from_storage(self.top_left, rotated)
+    }
+
+    /// Returns a new [`CCanvasAt`] with the pixels of the [`CCanvasAt`] transformed
+    /// by the `affine` transformation (see [`Affine`]) relative to its top left corner.
+    ///
+    /// Transformed pixels outside of the new width (`NW`) and height (`NH`) are dropped.
+    /// The pixels are resampled using the nearest pixel, so transparent pixels stay transparent.
+    ///
+    /// Returns [`None`] if the transformation can't be inverted
+    /// or the transformed bounding box doesn't fit in the [`Point`] coordinates.
+    pub fn transform_affine<const NW: usize, const NH: usize>(
+        &self,
+        affine: &Affine,
+    ) -> Option<CCanvasAt<C, NW, NH>> {
+        transform_affine(affine, self.storage(), self.top_left, |_| [[None; NH]; NW])
     }
 }
 
-impl<C, const W: usize, const H: usize> Drawable for CCanvasAt<C, W, H>
+impl<C, const N: usize> CCanvasAt<C, N, N>
 where
     C: PixelColor,
 {
-    type Color = C;
-    type Output = ();
-
-    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
-    where
-        D: DrawTarget<Color = C>,
-    {
-        let pixels_iter = self.bounding_box().points().filter_map(|point| {
-            // for the drawing position we need to account for the top_left offset of the drawn display
-            self.get_pixel(point).map(|color| Pixel(point, color))
-        });
-
-        target.draw_iter(pixels_iter)
+    /// Rotates the square [`CCanvasAt`] by 90 degrees clockwise in place.
+    pub fn rotate_90_in_place(&mut self) {
+        rotate_90_in_place(self.storage_mut());
+    }
+
+    /// Rotates the square [`CCanvasAt`] by 270 degrees clockwise
+    /// (90 degrees counterclockwise) in place.
+    pub fn rotate_270_in_place(&mut self) {
+        rotate_270_in_place(self.storage_mut());
     }
 }
 
-#[cfg(feature = "transform")]
-#[cfg_attr(docsrs, doc(cfg(feature = "transform")))]
-impl<C: PixelColor, const W: usize, const H: usize> embedded_graphics::transform::Transform
-    for CCanvasAt<C, W, H>
+/// Returns the `pixels` of a canvas rotated by `rotation`,
+/// the new width (`RW`) and height (`RH`) must match the rotated size.
+fn rotate_pixels<C, const W: usize, const H: usize, const RW: usize, const RH: usize>(
+    pixels: &[[Option<C>; H]; W],
+    rotation: Rotation,
+) -> [[Option<C>; RH]; RW]
+where
+    C: PixelColor,
 {
-    fn translate(&self, by: Point) -> Self {
-        Self {
-            // update the CanvasAt top_left!
-            top_left: self.top_left + by,
-            size: self.size,
-            pixels: self.pixels,
+    let mut rotated = [[None; RH]; RW];
+    rotate_into(pixels, &mut rotated, rotation);
+
+    rotated
+}
+
+/// Rotates the pixels of a square canvas by 90 degrees clockwise in place.
+fn rotate_90_in_place<C, const N: usize>(pixels: &mut [[Option<C>; N]; N]) {
+    transpose(pixels);
+    pixels.reverse();
+}
+
+/// Rotates the pixels of a square canvas by 270 degrees clockwise in place.
+fn rotate_270_in_place<C, const N: usize>(pixels: &mut [[Option<C>; N]; N]) {
+    transpose(pixels);
+    pixels.iter_mut().for_each(|column| column.reverse());
+}
+
+/// Transposes the pixels of a square canvas in place.
+fn transpose<C, const N: usize>(pixels: &mut [[Option<C>; N]; N]) {
+    for x in 1..N {
+        let (columns, rest) = pixels.split_at_mut(x);
+
+        // swap the pixels of column `x` above the diagonal with the ones in row `x`
+        for (color, column) in rest[0].iter_mut().zip(columns.iter_mut()) {
+            core::mem::swap(color, &mut column[x]);
         }
     }
+}
 
-    fn translate_mut(&mut self, by: Point) -> &mut Self {
-        self.top_left += by;
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+
+    use crate::{BlendMode, Wrap};
+
+    use super::*;
+
+    #[test]
+    fn test_ccanvas_at_crop() {
+        let mut canvas = CCanvasAt::<BinaryColor, 10, 10>::new(Point::new(5, 5));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(5, 5), BinaryColor::On),
+                Pixel(Point::new(8, 9), BinaryColor::Off),
+                Pixel(Point::new(14, 14), BinaryColor::On),
+            ])
+            .unwrap();
+
+        let area = Rectangle::new(Point::new(7, 8), Size::new(4, 4));
+        let cropped = canvas.crop::<4, 4>(&area).expect("Should crop");
+
+        assert_eq!(area, cropped.bounding_box());
+        assert_eq!(Some(BinaryColor::Off), cropped.get_pixel(Point::new(8, 9)));
+        assert_eq!(None, cropped.get_pixel(Point::new(5, 5)));
+        assert_eq!(None, cropped.get_pixel(Point::new(14, 14)));
+
+        // area larger than the new canvas only copies what fits
+        let smaller = canvas.crop::<2, 2>(&area).expect("Should crop partially");
+        assert_eq!(
+            Rectangle::new(area.top_left, Size::new(2, 2)),
+            smaller.bounding_box()
+        );
+        assert_eq!(Some(BinaryColor::Off), smaller.get_pixel(Point::new(8, 9)));
+
+        assert!(canvas
+            .crop::<4, 4>(&Rectangle::new(Point::new(7, 8), Size::zero()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_ccanvas_at_try_crop() {
+        let canvas =
+            CCanvasAt::<BinaryColor, 10, 10>::with_default_color(Point::new(5, 5), BinaryColor::On);
+
+        let area = Rectangle::new(Point::new(7, 8), Size::new(4, 3));
+
+        let cropped = canvas.try_crop::<4, 3>(&area).expect("Sizes match");
+        assert_eq!(area, cropped.bounding_box());
+
+        assert_eq!(
+            Err(CropError::SizeMismatch {
+                expected: Size::new(3, 3),
+                actual: Size::new(4, 3),
+            }),
+            canvas.try_crop::<3, 3>(&area).map(|_| ())
+        );
+        assert_eq!(
+            Err(CropError::EmptyArea),
+            canvas
+                .try_crop::<0, 0>(&Rectangle::new(Point::zero(), Size::zero()))
+                .map(|_| ())
+        );
+        // an empty area is reported before comparing the sizes
+        assert_eq!(
+            Err(CropError::EmptyArea),
+            canvas
+                .try_crop::<4, 3>(&Rectangle::new(Point::new(7, 8), Size::new(4, 0)))
+                .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_ccanvas_at_fill_contiguous_and_solid() {
+        use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+        // partially outside of the canvas on every side
+        let area = Rectangle::new(Point::new(3, -2), Size::new(6, 9));
+        let colors = (0..=u8::MAX).map(Gray8::new);
+
+        let mut expected = CCanvasAt::<Gray8, 4, 5>::new(Point::new(4, -1));
+        expected
+            .draw_iter(
+                area.points()
+                    .zip(colors.clone())
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+            .unwrap();
+
+        let mut canvas = CCanvasAt::<Gray8, 4, 5>::new(Point::new(4, -1));
+        canvas.fill_contiguous(&area, colors).unwrap();
+        assert_eq!(expected.storage(), canvas.storage());
+
+        let mut canvas = CCanvas::<Gray8, 4, 5>::new();
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(9, 2)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+        assert_eq!(
+            [None, Some(Gray8::WHITE), Some(Gray8::WHITE), None, None],
+            canvas.storage()[3]
+        );
+        assert_eq!([None; 5], canvas.storage()[0]);
+
+        canvas.clear(Gray8::BLACK).unwrap();
+        assert_eq!([[Some(Gray8::BLACK); 5]; 4], *canvas.storage());
+    }
+
+    #[test]
+    fn test_ccanvas_at_draw_with_blend() {
+        use embedded_graphics_core::pixelcolor::Gray8;
+
+        let canvas =
+            CCanvasAt::<Gray8, 3, 1>::with_default_color(Point::new(1, 0), Gray8::new(100));
+
+        // only the middle pixel of the target is drawn
+        let mut target = CCanvasAt::<Gray8, 3, 1>::new(Point::zero());
+        Pixel(Point::new(1, 0), Gray8::new(50))
+            .draw(&mut target)
+            .unwrap();
+        canvas.draw_with_blend(&mut target, BlendMode::Add).unwrap();
+
+        assert_eq!(None, target.get_pixel(Point::zero()));
+        assert_eq!(Some(Gray8::new(150)), target.get_pixel(Point::new(1, 0)));
+        // drawn over the undrawn pixel as it is
+        assert_eq!(Some(Gray8::new(100)), target.get_pixel(Point::new(2, 0)));
+    }
+
+    #[test]
+    fn test_ccanvas_rotate() {
+        let mut canvas = CCanvas::<BinaryColor, 3, 2>::new();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 0), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let rotated: CCanvas<BinaryColor, 2, 3> = canvas.rotate_90();
+        assert_eq!(Size::new(2, 3), rotated.size());
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(1, 2)));
+
+        let rotated = canvas.rotate_270();
+        assert_eq!(Some(BinaryColor::On), rotated.get_pixel(Point::new(0, 2)));
+        assert_eq!(Some(BinaryColor::Off), rotated.get_pixel(Point::new(0, 0)));
+
+        let rotated = canvas.rotate_180();
+        let mut in_place = canvas.place_at(Point::zero());
+        in_place.rotate_180_in_place();
+        assert_eq!(rotated.storage(), in_place.storage());
+
+        let mut square = CCanvasAt::<BinaryColor, 2, 2>::new(Point::new(1, 1));
+        Pixel(Point::new(1, 1), BinaryColor::On)
+            .draw(&mut square)
+            .unwrap();
+        let rotated = square.rotate_90();
+        square.rotate_90_in_place();
+        assert_eq!(rotated.storage(), square.storage());
+        assert_eq!(Some(BinaryColor::On), square.get_pixel(Point::new(2, 1)));
+
+        square.rotate_270_in_place();
+        assert_eq!(Some(BinaryColor::On), square.get_pixel(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn test_ccanvas_flip() {
+        let mut canvas = CCanvas::<BinaryColor, 3, 2>::new();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let flipped = canvas.flip_horizontal();
+        assert_eq!(Some(BinaryColor::On), flipped.get_pixel(Point::new(2, 0)));
+        assert_eq!(Some(BinaryColor::Off), flipped.get_pixel(Point::new(1, 1)));
+
+        let mut canvas_at = canvas.place_at(Point::new(1, 1));
+        canvas_at.flip_vertical_in_place();
+        assert_eq!(canvas.flip_vertical().storage(), canvas_at.storage());
+        assert_eq!(Some(BinaryColor::On), canvas_at.get_pixel(Point::new(1, 2)));
+        assert_eq!(
+            Some(BinaryColor::Off),
+            canvas_at.get_pixel(Point::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn test_ccanvas_scroll() {
+        let mut canvas = CCanvas::<BinaryColor, 3, 2>::new();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(2, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let mut canvas_at = canvas.place_at(Point::zero());
+        canvas_at.scroll(1, -1, Wrap::Yes);
+        assert_eq!(Some(BinaryColor::On), canvas_at.get_pixel(Point::new(1, 1)));
+        assert_eq!(
+            Some(BinaryColor::Off),
+            canvas_at.get_pixel(Point::new(0, 0))
+        );
+
+        canvas.scroll(0, 1, Wrap::No);
+        assert_eq!(Some(BinaryColor::On), canvas.get_pixel(Point::new(0, 1)));
+        assert_eq!(None, canvas.get_pixel(Point::new(2, 1)));
+    }
 
-        self
+    #[test]
+    fn test_ccanvas_blit() {
+        let mut source = CCanvas::<BinaryColor, 2, 2>::new();
+        source
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(1, 1), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        let mut canvas = CCanvas::<BinaryColor, 3, 3>::new();
+        canvas.blit(&source, &source.bounding_box(), Point::new(2, 1));
+        assert_eq!(Some(BinaryColor::On), canvas.get_pixel(Point::new(2, 1)));
+        assert_eq!(1, canvas.storage().iter().flatten().flatten().count());
+
+        let mut canvas_at = CCanvasAt::<BinaryColor, 3, 3>::new(Point::new(5, 5));
+        canvas_at.blit(
+            &source,
+            &Rectangle::new(Point::new(1, 1), Size::new(1, 1)),
+            Point::new(7, 7),
+        );
+        assert_eq!(
+            Some(BinaryColor::Off),
+            canvas_at.get_pixel(Point::new(7, 7))
+        );
     }
 }
@@ -0,0 +1,237 @@
+//! Diffing of canvases for minimal display updates
+use core::iter;
+
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+use crate::{
+    canvas::GenericCanvasAt,
+    storage::PixelStorage,
+    utils::{extend_dirty, non_empty},
+};
+
+/// A change of a pixel between the previous and the next [`GenericCanvasAt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelChange<C: PixelColor> {
+    /// The pixel is drawn with a different color in the next [`GenericCanvasAt`].
+    Drawn(Pixel<C>),
+    /// The pixel was drawn in the previous [`GenericCanvasAt`] but it's transparent
+    /// in the next one, so it must be restored from the background.
+    Cleared(Point),
+}
+
+impl<C: PixelColor> PixelChange<C> {
+    /// Returns the [`Point`] of the changed pixel on the display.
+    pub fn point(&self) -> Point {
+        match self {
+            PixelChange::Drawn(Pixel(point, _)) => *point,
+            PixelChange::Cleared(point) => *point,
+        }
+    }
+}
+
+/// The difference between the previous and the next [`GenericCanvasAt`] (see [`diff`]).
+#[derive(Debug)]
+pub struct Diff<'a, S> {
+    previous: &'a GenericCanvasAt<S>,
+    next: &'a GenericCanvasAt<S>,
+    area: Rectangle,
+}
+
+impl<S> Clone for Diff<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Diff<'_, S> {}
+
+/// Compares the `previous` and the `next` [`GenericCanvasAt`] on the display,
+/// e.g. two [`CanvasAt`](crate::CanvasAt)s or [`CCanvasAt`](crate::CCanvasAt)s.
+///
+/// The canvases can have different sizes and locations on the display,
+/// the union of both bounding boxes is compared.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{diff, CCanvasAt};
+/// use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+///
+/// let mut previous = CCanvasAt::<BinaryColor, 10, 10>::new(Point::zero());
+/// Pixel(Point::new(1, 1), BinaryColor::On).draw(&mut previous)?;
+///
+/// let mut next = CCanvasAt::<BinaryColor, 10, 10>::new(Point::zero());
+/// Pixel(Point::new(2, 1), BinaryColor::On).draw(&mut next)?;
+///
+/// let diff = diff(&previous, &next);
+/// assert_eq!(2, diff.pixels().count());
+///
+/// // restore the cleared pixel with the background color of the display
+/// let mut display = CCanvasAt::<BinaryColor, 10, 10>::new(Point::zero());
+/// diff.draw(&mut display, |_point| Some(BinaryColor::Off))?;
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+pub fn diff<'a, S: PixelStorage>(
+    previous: &'a GenericCanvasAt<S>,
+    next: &'a GenericCanvasAt<S>,
+) -> Diff<'a, S> {
+    let mut area = non_empty(previous.bounding_box());
+    extend_dirty(&mut area, next.bounding_box());
+
+    Diff {
+        previous,
+        next,
+        area: area.unwrap_or_default(),
+    }
+}
+
+impl<'a, S: PixelStorage> Diff<'a, S> {
+    /// Returns the compared area on the display, i.e. the union of the
+    /// bounding boxes of the previous and the next [`GenericCanvasAt`].
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Returns the change of the pixel at the given [`Point`] on the display.
+    pub fn pixel(&self, point: Point) -> Option<PixelChange<S::Color>> {
+        let next = self.next.get_pixel(point);
+
+        if self.previous.get_pixel(point) == next {
+            return None;
+        }
+
+        match next {
+            Some(color) => Some(PixelChange::Drawn(Pixel(point, color))),
+            None => Some(PixelChange::Cleared(point)),
+        }
+    }
+
+    /// Returns all the changed pixels.
+    pub fn pixels(&self) -> impl Iterator<Item = PixelChange<S::Color>> + 'a {
+        let diff = *self;
+
+        self.area
+            .points()
+            .filter_map(move |point| diff.pixel(point))
+    }
+
+    /// Returns the horizontal spans (with a height of 1) of changed pixels.
+    pub fn spans(&self) -> impl Iterator<Item = Rectangle> + 'a {
+        let diff = *self;
+        let start_x = self.area.top_left.x;
+        let end_x = start_x + self.area.size.width as i32;
+
+        self.area.rows().flat_map(move |y| {
+            let mut x = start_x;
+
+            iter::from_fn(move || {
+                // skip the unchanged pixels
+                while x < end_x && diff.pixel(Point::new(x, y)).is_none() {
+                    x += 1;
+                }
+
+                let span_start = x;
+                while x < end_x && diff.pixel(Point::new(x, y)).is_some() {
+                    x += 1;
+                }
+
+                let width = (x - span_start) as u32;
+                (width > 0).then(|| Rectangle::new(Point::new(span_start, y), Size::new(width, 1)))
+            })
+        })
+    }
+
+    /// Returns the bounding box of all the changed pixels.
+    ///
+    /// Returns [`None`] if nothing has changed.
+    pub fn bounding_box(&self) -> Option<Rectangle> {
+        self.spans().fold(None, |mut bounding_box, span| {
+            extend_dirty(&mut bounding_box, span);
+
+            bounding_box
+        })
+    }
+
+    /// Returns whether the previous and the next [`GenericCanvasAt`] look the same on the display.
+    pub fn is_empty(&self) -> bool {
+        self.pixels().next().is_none()
+    }
+
+    /// Draws only the changed pixels on the display.
+    ///
+    /// The cleared pixels are restored using the color returned by `background`
+    /// for the [`Point`] on the display, if it returns [`None`] the pixel is skipped.
+    pub fn draw<D, B>(&self, target: &mut D, background: B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color>,
+        B: Fn(Point) -> Option<S::Color>,
+    {
+        let pixels_iter = self.pixels().filter_map(|change| match change {
+            PixelChange::Drawn(pixel) => Some(pixel),
+            PixelChange::Cleared(point) => background(point).map(|color| Pixel(point, color)),
+        });
+
+        target.draw_iter(pixels_iter)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use alloc::vec::Vec;
+
+    use embedded_graphics_core::pixelcolor::BinaryColor;
+
+    use super::*;
+    use crate::CanvasAt;
+
+    #[test]
+    fn test_diff() {
+        let mut previous = CanvasAt::new(Point::new(0, 0), Size::new(4, 4));
+        previous
+            .draw_iter([
+                Pixel(Point::new(1, 1), BinaryColor::On),
+                Pixel(Point::new(2, 1), BinaryColor::On),
+                Pixel(Point::new(3, 3), BinaryColor::Off),
+            ])
+            .unwrap();
+
+        // moved 2 pixels to the right
+        let mut next = CanvasAt::new(Point::new(2, 0), Size::new(4, 4));
+        next.draw_iter([
+            Pixel(Point::new(2, 1), BinaryColor::On),
+            Pixel(Point::new(3, 1), BinaryColor::Off),
+            Pixel(Point::new(3, 3), BinaryColor::Off),
+        ])
+        .unwrap();
+
+        let diff = diff(&previous, &next);
+        assert_eq!(Rectangle::new(Point::zero(), Size::new(6, 4)), diff.area());
+        assert_eq!(
+            [
+                PixelChange::Cleared(Point::new(1, 1)),
+                PixelChange::Drawn(Pixel(Point::new(3, 1), BinaryColor::Off)),
+            ],
+            diff.pixels().collect::<Vec<_>>()[..]
+        );
+        assert_eq!(
+            [
+                Rectangle::new(Point::new(1, 1), Size::new(1, 1)),
+                Rectangle::new(Point::new(3, 1), Size::new(1, 1)),
+            ],
+            diff.spans().collect::<Vec<_>>()[..]
+        );
+        assert_eq!(
+            Some(Rectangle::new(Point::new(1, 1), Size::new(3, 1))),
+            diff.bounding_box()
+        );
+
+        let mut display =
+            CanvasAt::with_default_color(Point::zero(), Size::new(6, 4), BinaryColor::On);
+        diff.draw(&mut display, |_| Some(BinaryColor::Off)).unwrap();
+        assert_eq!(Some(BinaryColor::Off), display.get_pixel(Point::new(1, 1)));
+        assert_eq!(Some(BinaryColor::On), display.get_pixel(Point::new(2, 1)));
+        assert_eq!(Some(BinaryColor::Off), display.get_pixel(Point::new(3, 1)));
+
+        assert!(super::diff(&next, &next).is_empty());
+    }
+}
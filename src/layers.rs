@@ -0,0 +1,391 @@
+//! Compositing of [`CanvasAt`] layers.
+use alloc::vec::Vec;
+
+use embedded_graphics_core::{image::GetPixel, prelude::*, primitives::Rectangle};
+
+#[cfg(doc)]
+use crate::CanvasAt;
+use crate::{
+    blend::{self, Alpha, AlphaBlend},
+    canvas::GenericCanvasAt,
+    storage::PixelStorage,
+    utils::{draw_spans, extend_dirty},
+};
+
+/// The identifier of a [`Layer`] in [`Layers`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayerId(usize);
+
+/// A canvas, e.g. a [`CanvasAt`], in [`Layers`] with its z-index, visibility and opacity.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct Layer<S> {
+    /// The canvas of the [`Layer`].
+    pub canvas: GenericCanvasAt<S>,
+    /// Whether the [`Layer`] is drawn.
+    pub visible: bool,
+    /// The opacity of the [`Layer`] where `0` is fully transparent and [`u8::MAX`] is opaque.
+    pub opacity: u8,
+    z_index: i32,
+    id: LayerId,
+}
+
+impl<S> Layer<S> {
+    /// Returns the identifier of the [`Layer`].
+    pub fn id(&self) -> LayerId {
+        self.id
+    }
+
+    /// Returns the z-index of the [`Layer`], layers with higher z-index are drawn on top
+    /// (see [`Layers::set_z_index`]).
+    pub fn z_index(&self) -> i32 {
+        self.z_index
+    }
+}
+
+/// An ordered stack of canvas layers, e.g. [`CanvasAt`]s, e.g. a background, the content and an overlay,
+/// which is flattened when drawn on the display.
+///
+/// Each display pixel is drawn at most once and only the pixels inside the
+/// bounding box of the visible layers (see [`Layers::bounding_box`]) are drawn.
+/// A drawn pixel of a [`Layer`] is blended with the pixel of the layers below it using
+/// the [`Layer::opacity`] and the flattened pixels keep the opacity of the layers,
+/// so they're blended over the display with [`Layers::draw_blended`].
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{CanvasAt, Layers};
+/// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+///
+/// let mut layers = Layers::new();
+/// let background = layers.push(
+///     CanvasAt::with_default_color(Point::zero(), Size::new(64, 64), Rgb565::BLUE),
+///     0,
+/// );
+/// let toast = layers.push(CanvasAt::new(Point::new(8, 48), Size::new(48, 12)), 10);
+///
+/// let toast = layers.get_mut(toast).unwrap();
+/// toast.opacity = 192;
+/// Pixel(Point::new(10, 50), Rgb565::WHITE).draw(&mut toast.canvas)?;
+///
+/// let mut display = CanvasAt::new(Point::zero(), Size::new(64, 64));
+/// layers.draw_blended(&mut display)?;
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct Layers<S> {
+    /// The layers ordered from the bottom to the top.
+    layers: Vec<Layer<S>>,
+    next_id: usize,
+}
+
+impl<S> Default for Layers<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layers<S> {
+    /// Create a new empty [`Layers`].
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a visible and opaque [`Layer`] with the given z-index and returns its identifier.
+    ///
+    /// Layers with the same z-index are drawn in the order they were added.
+    pub fn push(&mut self, canvas: GenericCanvasAt<S>, z_index: i32) -> LayerId {
+        let id = LayerId(self.next_id);
+        self.next_id += 1;
+
+        self.insert(Layer {
+            canvas,
+            visible: true,
+            opacity: u8::MAX,
+            z_index,
+            id,
+        });
+
+        id
+    }
+
+    /// Removes the [`Layer`] and returns it.
+    ///
+    /// Returns [`None`] if there's no [`Layer`] with the given identifier.
+    pub fn remove(&mut self, id: LayerId) -> Option<Layer<S>> {
+        let index = self.index(id)?;
+
+        Some(self.layers.remove(index))
+    }
+
+    /// Returns the [`Layer`] with the given identifier.
+    pub fn get(&self, id: LayerId) -> Option<&Layer<S>> {
+        self.layers.iter().find(|layer| layer.id == id)
+    }
+
+    /// Returns the [`Layer`] with the given identifier for modifying
+    /// its canvas, visibility or opacity.
+    pub fn get_mut(&mut self, id: LayerId) -> Option<&mut Layer<S>> {
+        self.layers.iter_mut().find(|layer| layer.id == id)
+    }
+
+    /// Changes the z-index of the [`Layer`] and moves it on top of the
+    /// layers with the same z-index.
+    ///
+    /// Returns [`None`] if there's no [`Layer`] with the given identifier.
+    pub fn set_z_index(&mut self, id: LayerId, z_index: i32) -> Option<()> {
+        let mut layer = self.remove(id)?;
+        layer.z_index = z_index;
+        self.insert(layer);
+
+        Some(())
+    }
+
+    /// Returns an iterator over the layers from the bottom to the top.
+    pub fn iter(&self) -> impl Iterator<Item = &Layer<S>> {
+        self.layers.iter()
+    }
+
+    /// Returns the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns whether there are no layers.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    fn index(&self, id: LayerId) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.id == id)
+    }
+
+    /// Inserts the layer on top of the layers with lower or the same z-index.
+    fn insert(&mut self, layer: Layer<S>) {
+        let index = self
+            .layers
+            .partition_point(|other| other.z_index <= layer.z_index);
+
+        self.layers.insert(index, layer);
+    }
+
+    /// Returns the layers which are drawn from the bottom to the top.
+    fn visible_layers(&self) -> impl Iterator<Item = &Layer<S>> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.visible && layer.opacity != 0)
+    }
+}
+
+impl<S> Layers<S>
+where
+    S: PixelStorage,
+    S::Color: AlphaBlend,
+{
+    /// Returns the flattened color of the pixel at a given [`Point`] on the display
+    /// with the combined opacity of the layers.
+    ///
+    /// Returns [`None`] if none of the visible layers has a drawn pixel at the [`Point`].
+    pub fn get_pixel(&self, point: Point) -> Option<Alpha<S::Color>> {
+        self.visible_layers().fold(None, |below, layer| {
+            let color = match layer.canvas.get_pixel(point) {
+                Some(color) => Alpha::new(color, layer.opacity),
+                None => return below,
+            };
+
+            match below {
+                Some(below) => Some(composite(color, below)),
+                None => Some(color),
+            }
+        })
+    }
+
+    /// Draws the flattened layers on the display by blending each pixel over
+    /// the pixel already drawn on the display.
+    ///
+    /// The display pixels are read back using [`GetPixel`], pixels over
+    /// undrawn display pixels are drawn without blending and
+    /// pixels outside of the display are skipped.
+    pub fn draw_blended<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color> + GetPixel<Color = S::Color>,
+    {
+        let pixels_iter = self
+            .bounding_box()
+            .points()
+            .filter_map(|point| self.get_pixel(point).map(|color| Pixel(point, color)));
+
+        blend::draw_blended(pixels_iter, target)
+    }
+}
+
+/// Composites the `color` over the `below` color, both with an opacity.
+fn composite<C: AlphaBlend>(color: Alpha<C>, below: Alpha<C>) -> Alpha<C> {
+    let (alpha, below_alpha) = (u32::from(color.alpha), u32::from(below.alpha));
+    let max = u32::from(u8::MAX);
+
+    // the opacity of the color over the below color, it's at least `alpha`
+    let combined = alpha + (below_alpha * (max - alpha) + max / 2) / max;
+    if combined == 0 {
+        return below;
+    }
+    // the weight of the color in the composited color
+    let weight = ((alpha * max + combined / 2) / combined).min(max);
+
+    Alpha::new(
+        color.color.alpha_blend(below.color, weight as u8),
+        combined.min(max) as u8,
+    )
+}
+
+impl<S> Dimensions for Layers<S>
+where
+    S: PixelStorage,
+    S::Color: AlphaBlend,
+{
+    /// Returns the bounding box of the visible layers.
+    ///
+    /// If there are no visible layers, the bounding box is [`Rectangle::zero()`].
+    fn bounding_box(&self) -> Rectangle {
+        self.visible_layers()
+            .fold(None, |mut bounding_box, layer| {
+                extend_dirty(&mut bounding_box, layer.canvas.bounding_box());
+
+                bounding_box
+            })
+            .unwrap_or_else(Rectangle::zero)
+    }
+}
+
+impl<S> GetPixel for Layers<S>
+where
+    S: PixelStorage,
+    S::Color: AlphaBlend,
+{
+    type Color = Alpha<S::Color>;
+
+    fn pixel(&self, point: Point) -> Option<Self::Color> {
+        self.get_pixel(point)
+    }
+}
+
+impl<S> Drawable for Layers<S>
+where
+    S: PixelStorage,
+    S::Color: AlphaBlend,
+{
+    type Color = Alpha<S::Color>;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        draw_spans(&self.bounding_box(), |point| self.get_pixel(point), target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use super::*;
+    use crate::CanvasAt;
+
+    #[test]
+    fn test_layers() {
+        let mut layers = Layers::new();
+        let overlay = layers.push(CanvasAt::new(Point::new(2, 0), Size::new(2, 2)), 10);
+        let background = layers.push(
+            CanvasAt::with_default_color(Point::zero(), Size::new(3, 3), Gray8::BLACK),
+            0,
+        );
+        assert_eq!(
+            [background, overlay],
+            layers.iter().map(Layer::id).collect::<Vec<_>>()[..]
+        );
+        assert_eq!(
+            Rectangle::new(Point::zero(), Size::new(4, 3)),
+            layers.bounding_box()
+        );
+
+        let layer = layers.get_mut(overlay).unwrap();
+        layer.opacity = 128;
+        layer
+            .canvas
+            .draw_iter([
+                Pixel(Point::new(2, 0), Gray8::WHITE),
+                Pixel(Point::new(3, 0), Gray8::WHITE),
+            ])
+            .unwrap();
+
+        // blended with the background
+        assert_eq!(
+            Some(Alpha::opaque(Gray8::new(128))),
+            layers.get_pixel(Point::new(2, 0))
+        );
+        // nothing below it
+        assert_eq!(
+            Some(Alpha::new(Gray8::WHITE, 128)),
+            layers.get_pixel(Point::new(3, 0))
+        );
+        assert_eq!(None, layers.get_pixel(Point::new(3, 1)));
+
+        let mut display = CanvasAt::new(Point::zero(), Size::new(4, 3));
+        layers.draw_blended(&mut display).unwrap();
+        assert_eq!(10, display.storage().pixels().iter().flatten().count());
+
+        // move the background on top
+        layers.set_z_index(background, 20).unwrap();
+        assert_eq!(
+            Some(Alpha::opaque(Gray8::BLACK)),
+            layers.get_pixel(Point::new(2, 0))
+        );
+
+        layers.get_mut(background).unwrap().visible = false;
+        assert_eq!(
+            Rectangle::new(Point::new(2, 0), Size::new(2, 2)),
+            layers.bounding_box()
+        );
+
+        assert!(layers.remove(overlay).is_some());
+        assert!(layers.get(overlay).is_none());
+        assert_eq!(1, layers.len());
+        assert_eq!(Rectangle::zero(), layers.bounding_box());
+    }
+
+    #[test]
+    fn test_semi_transparent_layers() {
+        let mut layers = Layers::new();
+        let top = layers.push(
+            CanvasAt::with_default_color(Point::zero(), Size::new(2, 1), Gray8::WHITE),
+            1,
+        );
+        let bottom = layers.push(
+            CanvasAt::with_default_color(Point::zero(), Size::new(1, 1), Gray8::BLACK),
+            0,
+        );
+        layers.get_mut(top).unwrap().opacity = 128;
+        layers.get_mut(bottom).unwrap().opacity = 128;
+
+        // both layers cover 75% of the display pixel
+        assert_eq!(
+            Some(Alpha::new(Gray8::new(170), 192)),
+            layers.get_pixel(Point::zero())
+        );
+
+        // the semi-transparent layer is blended with the display
+        // although there's no layer below it
+        let mut display =
+            CanvasAt::with_default_color(Point::zero(), Size::new(2, 1), Gray8::BLACK);
+        layers.draw_blended(&mut display).unwrap();
+        assert_eq!(Some(Gray8::new(128)), display.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Gray8::new(128)), display.get_pixel(Point::zero()));
+    }
+}
@@ -9,10 +9,25 @@
 //! Based on [`embedded-graphics-core`] and [`embedded-graphics`]
 //! (see `transform` feature in [Crate features](#crate-features)).
 //!
-//! This crate is `no_std` and it has 2 sets of canvases:
+//! This crate is `no_std` and every canvas is a [`GenericCanvas`] / [`GenericCanvasAt`]
+//! over a [`PixelStorage`], so they all share the same features:
 //! - [`Canvas`] and [`CanvasAt`] - require `alloc` feature
 //! - [`CCanvas`] and [`CCanvasAt`] - do **not** require `alloc` feature because they
 //!   use const generics instead.
+//! - [`SliceCanvas`] and [`SliceCanvasAt`] - do **not** require `alloc` feature because
+//!   they draw on a slice of pixels you provide, e.g. a framebuffer in a specific RAM section.
+//!
+//! And memory efficient alternatives with the same drawing API:
+//! - [`MaskedCanvas`] and [`MaskedCanvasAt`] - require `alloc` feature and store
+//!   the colors in a dense buffer with a 1 bit per pixel mask of the drawn pixels.
+//! - [`BinaryCanvas`] and [`BinaryCanvasAt`] (require `alloc` feature), [`CBinaryCanvas`] and
+//!   [`CBinaryCanvasAt`] - specialised for [`BinaryColor`](embedded_graphics_core::pixelcolor::BinaryColor),
+//!   they pack the color and transparency in 2 bits per pixel using the page layout of
+//!   monochrome display controllers.
+//!
+//! You can also plug in your own [`PixelStorage`], e.g. a packed buffer or an external memory.
+//! The methods creating new canvases (e.g. cropping, resizing or rotating by 90 degrees)
+//! require a [`NewPixelStorage`].
 //!
 //! The main advantages of the canvases in this crate are:
 //!
@@ -32,6 +47,9 @@
 //! _Example: Cropping text - The full canvas content is drawn on the left and
 //! only portion of it is cropped and drawn on the right._
 //!
+//! 3. **Alpha blending** - Draw with [`Alpha`] colors (color with coverage) and composite
+//!    the canvas over the pixels already drawn on the display, e.g. for anti-aliased icons and fades.
+//!
 //! [`embedded-graphics`]: https://crates.io/crates/embedded-graphics
 //! [`embedded-graphics-core`]: https://crates.io/crates/embedded-graphics-core
 //! [transparency_screenshot]: https://github.com/LechevSpace/embedded-canvas/raw/81de4494c36d0d76c552823b83d3ac446b27b636/examples/screenshots/transparency.png
@@ -50,6 +68,10 @@
 //! - `Canvas::place_at(top_left: Point) -> CanvasAt` (with `alloc` feature) or `CCanvas::place_at(top_left: Point) -> CCanvasAt`
 //! - `Canvas::place_center(center: Point) -> CanvasAt` (with `alloc` feature) or `CCanvas::place_center(center: Point) -> CCanvasAt`
 //!
+//! Or use it as an image source, since [`Canvas`]/[`CCanvas`] implement
+//! [`ImageDrawable`](embedded_graphics_core::image::ImageDrawable), e.g. with `Image::new(&canvas, top_left)`
+//! and `canvas.sub_image(&area)` from [`embedded-graphics`]. Transparent pixels are skipped.
+//!
 //! ## `CanvasAt` / `CCanavasAt`
 //!
 //! [`CanvasAt`]/[`CCanvasAt`] is a type of canvas ready to be drawn on the display
@@ -65,8 +87,9 @@
 //!
 //! # Crate features
 //! - `default` features - `transform`
-//! - `alloc` - enables [`Canvas`] and [`CanvasAt`].
-//! - `transform` - enables the trait implementation of [`embedded_graphics::transform::Transform`] for [`CanvasAt`]/[`CCanvasAt`].
+//! - `alloc` - enables [`Canvas`], [`CanvasAt`], [`MaskedCanvas`], [`MaskedCanvasAt`], [`BinaryCanvas`] and [`BinaryCanvasAt`].
+//! - `transform` - enables the trait implementation of [`embedded_graphics::transform::Transform`] for [`Canvas`]/[`CCanvas`] (translates the content)
+//!   and [`CanvasAt`]/[`CCanvasAt`] (translates the location on the display).
 //!
 //! [github]: https://img.shields.io/badge/github-3873AD?style=for-the-badge&labelColor=555555&logo=github
 //! [crates-io]: https://img.shields.io/crates/v/embedded-canvas?logo=rust&style=for-the-badge
@@ -86,12 +109,84 @@ extern crate alloc;
 pub use canvas::{Canvas, CanvasAt};
 
 #[doc(inline)]
-pub use consts::{CCanvas, CCanvasAt};
+pub use canvas::{GenericCanvas, GenericCanvasAt};
+
+#[doc(inline)]
+pub use diff::{diff, Diff, PixelChange};
+
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use layers::{Layer, LayerId, Layers};
+
+#[doc(inline)]
+pub use scale::Filter;
+
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use masked::{Masked, MaskedCanvas, MaskedCanvasAt};
+
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use binary::{BinaryCanvas, BinaryCanvasAt, Bitplanes};
+
+#[doc(inline)]
+pub use binary::{CBinaryCanvas, CBinaryCanvasAt, CBitplanes};
+
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use mask::BitMask;
+
+#[doc(inline)]
+pub use mask::{ClipMask, WithMask};
+
+#[doc(inline)]
+pub use affine::Affine;
+
+#[doc(inline)]
+pub use blend::{Alpha, AlphaBlend, Blend, BlendMode};
+
+#[doc(inline)]
+pub use clip::{ClipDepthError, ClipStack};
+
+#[doc(inline)]
+pub use consts::{CCanvas, CCanvasAt, CropError};
+
+#[doc(inline)]
+pub use scroll::Wrap;
+
+#[doc(inline)]
+pub use slice::{SliceCanvas, SliceCanvasAt};
+
+#[doc(inline)]
+pub use storage::{NewPixelStorage, PixelStorage, RowMajor};
+
+#[doc(inline)]
+pub use sub_canvas::{CSubCanvasMut, SubCanvasMut};
+
+#[doc(inline)]
+pub use tracked::Tracked;
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-mod canvas;
+mod layers;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+mod masked;
+
+mod affine;
+mod binary;
+mod blend;
+mod canvas;
+mod clip;
 mod consts;
+mod diff;
+mod mask;
+mod scale;
+mod scroll;
+mod slice;
+mod storage;
+mod sub_canvas;
+mod tracked;
 
 pub(crate) mod utils;
@@ -0,0 +1,394 @@
+//! Clip masks for drawing only inside of a shape.
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
+#[cfg(feature = "alloc")]
+use embedded_graphics_core::pixelcolor::BinaryColor;
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+#[cfg(feature = "alloc")]
+use crate::utils::{get_bit, mask_len, set_bit};
+use crate::{
+    canvas::{GenericCanvas, GenericCanvasAt},
+    storage::PixelStorage,
+};
+
+/// A mask of the points inside of which drawing is allowed (see [`WithMask`]).
+///
+/// Implemented for closures returning whether the [`Point`] is inside of the mask,
+/// [`BitMask`] and the canvases, where the drawn pixels are inside of the mask.
+pub trait ClipMask {
+    /// Returns whether the [`Point`] is inside of the mask.
+    fn contains(&self, point: Point) -> bool;
+}
+
+impl<F: Fn(Point) -> bool> ClipMask for F {
+    fn contains(&self, point: Point) -> bool {
+        self(point)
+    }
+}
+
+impl<S: PixelStorage> ClipMask for GenericCanvas<S> {
+    fn contains(&self, point: Point) -> bool {
+        self.get_pixel(point).is_some()
+    }
+}
+
+impl<S: PixelStorage> ClipMask for GenericCanvasAt<S> {
+    fn contains(&self, point: Point) -> bool {
+        self.get_pixel(point).is_some()
+    }
+}
+
+/// A mask with 1 bit per pixel on which you draw the shape of the mask,
+/// [`BinaryColor::On`] pixels are inside of the mask.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{BitMask, Canvas};
+/// use embedded_graphics_core::{
+///     pixelcolor::{BinaryColor, Rgb565},
+///     prelude::*,
+///     primitives::Rectangle,
+/// };
+///
+/// let area = Rectangle::new(Point::zero(), Size::new(32, 32));
+///
+/// // a circle with a radius of 16 pixels
+/// let mut mask = BitMask::new(area.size);
+/// mask.draw_iter(area.points().filter_map(|point| {
+///     let offset = point - Point::new(16, 16);
+///     (offset.x.pow(2) + offset.y.pow(2) <= 16 * 16).then_some(Pixel(point, BinaryColor::On))
+/// }))?;
+///
+/// // a round avatar
+/// let mut avatar = Canvas::new(area.size);
+/// avatar.with_mask(&mask).fill_solid(&area, Rgb565::GREEN)?;
+///
+/// assert_eq!(None, avatar.get_pixel(Point::zero()));
+/// assert_eq!(Some(Rgb565::GREEN), avatar.get_pixel(Point::new(16, 16)));
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMask {
+    /// The size of the [`BitMask`].
+    pub size: Size,
+    /// The bits of the [`BitMask`] row by row, the least significant bit first.
+    pub bits: Box<[u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl BitMask {
+    /// Create a new [`BitMask`] without any points inside of the mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics when width * height > [`usize::MAX`].
+    pub fn new(size: Size) -> Self {
+        let bits = size.width as usize * size.height as usize;
+
+        Self {
+            size,
+            bits: vec![0; mask_len(bits)].into_boxed_slice(),
+        }
+    }
+
+    fn point_to_index(&self, point: Point) -> Option<usize> {
+        let (x, y) = <(u32, u32)>::try_from(point).ok()?;
+
+        (x < self.size.width && y < self.size.height)
+            .then(|| x as usize + y as usize * self.size.width as usize)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ClipMask for BitMask {
+    fn contains(&self, point: Point) -> bool {
+        self.point_to_index(point)
+            .is_some_and(|index| get_bit(&self.bits, index))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OriginDimensions for BitMask {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DrawTarget for BitMask {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels.into_iter() {
+            if let Some(index) = self.point_to_index(point) {
+                set_bit(&mut self.bits, index, color.is_on());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`DrawTarget`] adapter which discards the pixels outside of the [`ClipMask`].
+///
+/// Created with the `with_mask` method of the canvases.
+#[derive(Debug)]
+pub struct WithMask<'a, T, M: ?Sized> {
+    target: &'a mut T,
+    mask: &'a M,
+}
+
+impl<'a, T, M: ClipMask + ?Sized> WithMask<'a, T, M> {
+    /// Create a new [`WithMask`] drawing on the `target` only inside of the `mask`.
+    pub fn new(target: &'a mut T, mask: &'a M) -> Self {
+        Self { target, mask }
+    }
+}
+
+impl<T: Dimensions, M: ?Sized> Dimensions for WithMask<'_, T, M> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<T: DrawTarget, M: ClipMask + ?Sized> DrawTarget for WithMask<'_, T, M> {
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mask = self.mask;
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| mask.contains(*point)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let bounds = area.intersection(&self.target.bounding_box());
+        if bounds.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut colors = colors.into_iter();
+        let skip = |colors: &mut I::IntoIter, count: usize| {
+            if count > 0 {
+                colors.nth(count - 1);
+            }
+        };
+
+        let area_end_x = area.top_left.x + area.size.width as i32;
+        let bounds_end_x = bounds.top_left.x + bounds.size.width as i32;
+        // the colors of the rows above the bounds
+        skip(
+            &mut colors,
+            (bounds.top_left.y - area.top_left.y) as usize * area.size.width as usize,
+        );
+
+        let mut row = None;
+        for (run, inside) in masked_runs(self.mask, bounds) {
+            if row != Some(run.top_left.y) {
+                if row.is_some() {
+                    // the colors right of the bounds of the previous row
+                    skip(&mut colors, (area_end_x - bounds_end_x) as usize);
+                }
+                // the colors left of the bounds
+                skip(&mut colors, (bounds.top_left.x - area.top_left.x) as usize);
+                row = Some(run.top_left.y);
+            }
+
+            let len = run.size.width as usize;
+            if inside {
+                let mut run_colors = colors.by_ref().take(len);
+                self.target.fill_contiguous(&run, &mut run_colors)?;
+                // the target might not consume all the colors, e.g. when it's clipped
+                run_colors.for_each(drop);
+            } else {
+                skip(&mut colors, len);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let bounds = area.intersection(&self.target.bounding_box());
+
+        for (run, inside) in masked_runs(self.mask, bounds) {
+            if inside {
+                self.target.fill_solid(&run, color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the horizontal runs of points of the `area` row by row, which are
+/// either all inside or all outside of the `mask`.
+fn masked_runs<'a, M: ClipMask + ?Sized>(
+    mask: &'a M,
+    area: Rectangle,
+) -> impl Iterator<Item = (Rectangle, bool)> + 'a {
+    let end_x = area.top_left.x + area.size.width as i32;
+
+    area.rows().flat_map(move |y| {
+        let mut x = area.top_left.x;
+
+        core::iter::from_fn(move || {
+            if x >= end_x {
+                return None;
+            }
+
+            let start = x;
+            let inside = mask.contains(Point::new(x, y));
+            x += 1;
+            while x < end_x && mask.contains(Point::new(x, y)) == inside {
+                x += 1;
+            }
+
+            let run = Rectangle::new(Point::new(start, y), Size::new((x - start) as u32, 1));
+            Some((run, inside))
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use crate::{CCanvas, CCanvasAt};
+
+    use super::*;
+
+    #[test]
+    fn test_with_mask_fill_contiguous() {
+        let mut canvas = CCanvas::<Gray8, 3, 3>::new();
+        let without_column = |point: Point| point.x != 1;
+
+        // partially outside of the canvas on the top and left sides
+        canvas
+            .with_mask(&without_column)
+            .fill_contiguous(
+                &Rectangle::new(Point::new(-1, -1), Size::new(5, 4)),
+                (0..20).map(Gray8::new),
+            )
+            .unwrap();
+
+        // the colors stay aligned with the points of the area
+        assert_eq!(Some(Gray8::new(6)), canvas.get_pixel(Point::new(0, 0)));
+        assert_eq!(None, canvas.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Gray8::new(8)), canvas.get_pixel(Point::new(2, 0)));
+        assert_eq!(Some(Gray8::new(18)), canvas.get_pixel(Point::new(2, 2)));
+    }
+
+    #[test]
+    fn test_with_mask_over_clip_stack() {
+        let mut canvas = CCanvas::<Gray8, 4, 1>::new();
+        let without_column = |point: Point| point.x != 1;
+
+        let mut clipped = canvas.clipped_stack::<1>();
+        clipped
+            .push(&Rectangle::new(Point::new(2, 0), Size::new(2, 1)))
+            .unwrap();
+        WithMask::new(&mut clipped, &without_column)
+            .fill_contiguous(
+                &Rectangle::new(Point::zero(), Size::new(4, 1)),
+                (10..14).map(Gray8::new),
+            )
+            .unwrap();
+
+        assert_eq!(None, canvas.get_pixel(Point::new(0, 0)));
+        assert_eq!(Some(Gray8::new(12)), canvas.get_pixel(Point::new(2, 0)));
+        assert_eq!(Some(Gray8::new(13)), canvas.get_pixel(Point::new(3, 0)));
+    }
+
+    #[test]
+    fn test_with_mask_outside_of_target() {
+        let mut canvas = CCanvasAt::<Gray8, 2, 2>::new(Point::new(10, 10));
+        let checked = core::cell::Cell::new(0);
+        let mask = |_point: Point| {
+            checked.set(checked.get() + 1);
+            true
+        };
+
+        // the mask is checked only inside of the target
+        let mut masked = canvas.with_mask(&mask);
+        masked
+            .fill_solid(
+                &Rectangle::new(Point::zero(), Size::new(10, 10)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+        masked
+            .fill_contiguous(
+                &Rectangle::new(Point::new(11, 0), Size::new(2, 20)),
+                core::iter::repeat(Gray8::WHITE),
+            )
+            .unwrap();
+        assert_eq!(2, checked.get());
+        assert_eq!(Some(Gray8::WHITE), canvas.get_pixel(Point::new(11, 11)));
+        assert_eq!(None, canvas.get_pixel(Point::new(10, 11)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bit_mask() {
+        let mut mask = BitMask::new(Size::new(3, 3));
+        mask.fill_solid(
+            &Rectangle::new(Point::new(1, 0), Size::new(2, 2)),
+            BinaryColor::On,
+        )
+        .unwrap();
+        Pixel(Point::new(2, 1), BinaryColor::Off)
+            .draw(&mut mask)
+            .unwrap();
+        assert!(mask.contains(Point::new(1, 1)));
+        assert!(!mask.contains(Point::new(2, 1)));
+        assert!(!mask.contains(Point::new(-1, 0)));
+
+        let mut canvas = crate::Canvas::new(Size::new(3, 3));
+        canvas
+            .with_mask(&mask)
+            .fill_contiguous(
+                &Rectangle::new(Point::zero(), Size::new(3, 3)),
+                (0..9).map(Gray8::new),
+            )
+            .unwrap();
+        assert_eq!(
+            [
+                None,
+                Some(1),
+                Some(2),
+                None,
+                Some(4),
+                None,
+                None,
+                None,
+                None
+            ],
+            canvas
+                .storage()
+                .pixels()
+                .iter()
+                .map(|c| c.map(|c| c.luma()))
+                .collect::<alloc::vec::Vec<_>>()[..]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! `Canvas`es storing the colors in a dense buffer with a bit mask of the drawn pixels
+use alloc::{boxed::Box, vec};
+
+use embedded_graphics_core::prelude::*;
+
+use crate::{
+    canvas::{GenericCanvas, GenericCanvasAt},
+    storage::{NewPixelStorage, PixelStorage},
+    utils::{get_bit, mask_len, set_bit},
+};
+
+/// A [`PixelStorage`] which stores the colors row by row in a dense buffer
+/// and a bit mask with 1 bit per pixel marking the drawn pixels.
+///
+/// For example a 320x240 `Rgb565` canvas takes 150 KiB for the colors and
+/// 9.4 KiB for the mask instead of 300 KiB.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct Masked<C> {
+    size: Size,
+    colors: Box<[C]>,
+    mask: Box<[u8]>,
+}
+
+impl<C: PixelColor> Masked<C> {
+    /// Returns the colors of the [`Masked`] storage, row by row.
+    ///
+    /// The color of a pixel which is not set in the [`Masked::mask`] is meaningless.
+    pub fn colors(&self) -> &[C] {
+        &self.colors
+    }
+
+    /// Returns the bit mask of the drawn pixels, 1 bit per pixel with the least
+    /// significant bit of each byte being the first pixel.
+    pub fn mask(&self) -> &[u8] {
+        &self.mask
+    }
+
+    fn index(&self, point: Point) -> usize {
+        point.x as usize + point.y as usize * self.size.width as usize
+    }
+}
+
+impl<C: PixelColor> PixelStorage for Masked<C> {
+    type Color = C;
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn get(&self, point: Point) -> Option<C> {
+        let index = self.index(point);
+
+        get_bit(&self.mask, index).then(|| self.colors[index])
+    }
+
+    fn set(&mut self, point: Point, color: Option<C>) {
+        let index = self.index(point);
+
+        if let Some(color) = color {
+            self.colors[index] = color;
+        }
+        set_bit(&mut self.mask, index, color.is_some());
+    }
+}
+
+impl<C: PixelColor + Default> NewPixelStorage for Masked<C> {
+    /// Create a new [`Masked`] storage, the colors of the transparent pixels
+    /// are set to the default color.
+    ///
+    /// # Panics
+    ///
+    /// Panics when width * height > [`usize::MAX`].
+    fn with_size(size: Size, color: Option<C>) -> Self {
+        let pixel_count = size.width as usize * size.height as usize;
+        let mask_byte = if color.is_some() { u8::MAX } else { 0 };
+        let mut mask = vec![mask_byte; mask_len(pixel_count)].into_boxed_slice();
+
+        // the bits past the last pixel are never set
+        let last_bits = pixel_count % 8;
+        if last_bits != 0 {
+            let last = mask.len() - 1;
+            mask[last] &= (1 << last_bits) - 1;
+        }
+
+        Self {
+            size,
+            colors: vec![color.unwrap_or_default(); pixel_count].into_boxed_slice(),
+            mask,
+        }
+    }
+}
+
+/// Canvas on which you can draw but it's not drawable on the display yet.
+///
+/// Same as [`Canvas`](crate::Canvas) but instead of storing an [`Option`] for
+/// each pixel, it stores the colors in a dense buffer and a bit mask with
+/// 1 bit per pixel marking the drawn pixels (see [`Masked`]).
+///
+/// Draw on the [`MaskedCanvas`] using origin of [`Point::zero()`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type MaskedCanvas<C> = GenericCanvas<Masked<C>>;
+
+/// Canvas which is drawable at the provided [`Point`] (location) on the display.
+///
+/// Same as [`CanvasAt`](crate::CanvasAt) but it stores the colors in a dense
+/// buffer and a bit mask of the drawn pixels (see [`Masked`]).
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type MaskedCanvasAt<C> = GenericCanvasAt<Masked<C>>;
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::{pixelcolor::Rgb565, primitives::Rectangle};
+
+    use super::*;
+
+    #[test]
+    fn test_masked_canvas() {
+        let mut canvas = MaskedCanvas::<Rgb565>::new(Size::new(10, 5));
+        assert_eq!(50, canvas.storage().colors().len());
+        assert_eq!(7, canvas.storage().mask().len());
+
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb565::RED),
+                Pixel(Point::new(9, 4), Rgb565::BLACK),
+                Pixel(Point::new(10, 4), Rgb565::GREEN),
+            ])
+            .unwrap();
+        assert_eq!(Some(Rgb565::RED), canvas.get_pixel(Point::new(0, 0)));
+        // the default color is never returned for pixels which were not drawn
+        assert_eq!(None, canvas.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Rgb565::BLACK), canvas.get_pixel(Point::new(9, 4)));
+
+        let cropped = canvas
+            .crop(&Rectangle::new(Point::new(8, 3), Size::new(2, 2)))
+            .expect("Should crop");
+        assert_eq!(Some(Rgb565::BLACK), cropped.get_pixel(Point::new(1, 1)));
+        assert_eq!(None, cropped.get_pixel(Point::new(0, 0)));
+
+        let canvas_at = canvas.place_at(Point::new(5, 5));
+        let mut target = MaskedCanvasAt::<Rgb565>::with_default_color(
+            Point::zero(),
+            Size::new(20, 20),
+            Rgb565::WHITE,
+        );
+        canvas_at.draw(&mut target).unwrap();
+        assert_eq!(Some(Rgb565::RED), target.get_pixel(Point::new(5, 5)));
+        assert_eq!(Some(Rgb565::WHITE), target.get_pixel(Point::new(6, 5)));
+        assert_eq!(Some(Rgb565::BLACK), target.get_pixel(Point::new(14, 9)));
+
+        // 3 pixels, the bits past them are not set
+        let filled = MaskedCanvas::with_default_color(Size::new(3, 1), Rgb565::BLUE);
+        assert_eq!(&[0b111], filled.storage().mask());
+        let filled = MaskedCanvas::with_default_color(Size::new(10, 1), Rgb565::BLUE);
+        assert_eq!(&[0xff, 0b11], filled.storage().mask());
+    }
+
+    #[test]
+    fn test_masked_canvas_rotate() {
+        let mut canvas = MaskedCanvas::<Rgb565>::new(Size::new(3, 2));
+        canvas
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb565::RED),
+                Pixel(Point::new(2, 1), Rgb565::BLUE),
+            ])
+            .unwrap();
+
+        let rotated = canvas.rotate_90();
+        assert_eq!(Size::new(2, 3), rotated.size());
+        assert_eq!(Some(Rgb565::RED), rotated.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Rgb565::BLUE), rotated.get_pixel(Point::new(0, 2)));
+        assert_eq!(None, rotated.get_pixel(Point::new(0, 0)));
+
+        let rotated = canvas.rotate_180();
+        assert_eq!(Some(Rgb565::RED), rotated.get_pixel(Point::new(2, 1)));
+        assert_eq!(Some(Rgb565::BLUE), rotated.get_pixel(Point::new(0, 0)));
+
+        let rotated = canvas.rotate_270();
+        assert_eq!(Some(Rgb565::RED), rotated.get_pixel(Point::new(0, 2)));
+        assert_eq!(Some(Rgb565::BLUE), rotated.get_pixel(Point::new(1, 0)));
+        assert_eq!(&[0b0001_0010], rotated.storage().mask());
+    }
+}
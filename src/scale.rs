@@ -0,0 +1,132 @@
+//! Scaling of canvases to a different size.
+use embedded_graphics_core::prelude::{Point, Size};
+
+use crate::blend::AlphaBlend;
+
+/// The filter used for scaling a canvas (see [`GenericCanvas::scale_to`](crate::GenericCanvas::scale_to)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Filter {
+    /// Takes the color of the nearest pixel and keeps the hard edges (e.g. for pixel art).
+    #[default]
+    Nearest,
+    /// Interpolates between the colors of the (up to) 4 nearest drawn pixels.
+    ///
+    /// A scaled pixel is transparent when the nearest pixel is transparent,
+    /// so the transparency of the canvas is preserved.
+    Bilinear,
+}
+
+/// The fractional bits of the fixed point positions in the source canvas.
+const FRACTION_BITS: u32 = 8;
+const ONE: i64 = 1 << FRACTION_BITS;
+
+/// Returns the fixed point position in the source of the center of the scaled pixel at `position`.
+fn source_position(position: i32, length: u32, scaled_length: u32) -> i64 {
+    (2 * i64::from(position) + 1) * i64::from(length) * ONE / (2 * i64::from(scaled_length))
+}
+
+/// Returns the [`Point`] in the source canvas with `size` which is the nearest
+/// to the `point` in the canvas scaled to `scaled_size`.
+pub(crate) fn nearest(size: Size, scaled_size: Size, point: Point) -> Point {
+    let x = source_position(point.x, size.width, scaled_size.width) >> FRACTION_BITS;
+    let y = source_position(point.y, size.height, scaled_size.height) >> FRACTION_BITS;
+
+    Point::new(x as i32, y as i32)
+}
+
+/// Returns the bilinear interpolated color of the `point` in the canvas scaled to `scaled_size`
+/// using the pixels of the source canvas with `size`.
+///
+/// Transparent pixels are skipped and the weights of the drawn pixels are normalized.
+pub(crate) fn bilinear<C, F>(size: Size, scaled_size: Size, point: Point, get_pixel: F) -> Option<C>
+where
+    C: AlphaBlend,
+    F: Fn(Point) -> Option<C>,
+{
+    // preserve the transparency of the nearest pixel
+    get_pixel(nearest(size, scaled_size, point))?;
+
+    let (x0, x1, fx) = neighbours(point.x, size.width, scaled_size.width);
+    let (y0, y1, fy) = neighbours(point.y, size.height, scaled_size.height);
+
+    let samples = [
+        (Point::new(x0, y0), (ONE - fx) * (ONE - fy)),
+        (Point::new(x1, y0), fx * (ONE - fy)),
+        (Point::new(x0, y1), (ONE - fx) * fy),
+        (Point::new(x1, y1), fx * fy),
+    ];
+
+    // running weighted mean of the drawn neighbours
+    let (color, _total) = samples
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0)
+        .filter_map(|(point, weight)| get_pixel(point).map(|color| (color, weight)))
+        .fold(None, |mean: Option<(C, i64)>, (color, weight)| match mean {
+            Some((mean, total)) => {
+                let total = total + weight;
+                let alpha = (weight * i64::from(u8::MAX) + total / 2) / total;
+
+                Some((color.alpha_blend(mean, alpha as u8), total))
+            }
+            None => Some((color, weight)),
+        })?;
+
+    Some(color)
+}
+
+/// Returns the 2 neighbouring positions in the source and the fixed point
+/// fraction of the distance from the first one.
+fn neighbours(position: i32, length: u32, scaled_length: u32) -> (i32, i32, i64) {
+    let last = i64::from(length) - 1;
+    let position =
+        (source_position(position, length, scaled_length) - ONE / 2).clamp(0, last * ONE);
+
+    let first = position >> FRACTION_BITS;
+    let fraction = position & (ONE - 1);
+
+    (first as i32, (first + 1).min(last) as i32, fraction)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use super::*;
+
+    #[test]
+    fn test_bilinear() {
+        // 2x1 source
+        let get_pixel = |point: Point| match point.x {
+            0 => Some(Gray8::BLACK),
+            1 => Some(Gray8::WHITE),
+            _ => None,
+        };
+        let size = Size::new(2, 1);
+        let scaled_size = Size::new(4, 1);
+
+        assert_eq!(
+            Point::new(0, 0),
+            nearest(size, scaled_size, Point::new(1, 0))
+        );
+        assert_eq!(
+            Point::new(1, 0),
+            nearest(size, scaled_size, Point::new(2, 0))
+        );
+
+        let colors = (0..4)
+            .map(|x| bilinear(size, scaled_size, Point::new(x, 0), get_pixel).map(|c| c.luma()))
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!([Some(0), Some(64), Some(191), Some(255)], colors[..]);
+
+        // the transparent neighbour is skipped
+        let get_pixel = |point: Point| (point.x == 0).then_some(Gray8::new(100));
+        assert_eq!(
+            Some(Gray8::new(100)),
+            bilinear(size, scaled_size, Point::new(1, 0), get_pixel)
+        );
+        assert_eq!(
+            None,
+            bilinear(size, scaled_size, Point::new(2, 0), get_pixel)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Scrolling of the canvas content.
+use embedded_graphics_core::prelude::Point;
+
+use crate::storage::PixelStorage;
+
+/// Whether the pixels scrolled outside of a canvas wrap around to the opposite side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    /// The pixels scrolled outside of the canvas appear on the opposite side.
+    Yes,
+    /// The pixels scrolled outside of the canvas are dropped
+    /// and the uncovered pixels are transparent.
+    No,
+}
+
+/// Shifts the `items` by the given amount in place,
+/// a positive amount shifts them towards the end.
+///
+/// When not wrapping, the uncovered items are set to `fill`.
+pub(crate) fn shift<T: Copy>(items: &mut [T], by: i64, wrap: Wrap, fill: T) {
+    let len = items.len();
+    if len == 0 || by == 0 {
+        return;
+    }
+
+    match wrap {
+        Wrap::Yes => items.rotate_right(by.rem_euclid(len as i64) as usize),
+        Wrap::No => {
+            let amount = by.unsigned_abs().min(len as u64) as usize;
+
+            if by > 0 {
+                items.copy_within(..len - amount, amount);
+                items[..amount].fill(fill);
+            } else {
+                items.copy_within(amount.., 0);
+                items[len - amount..].fill(fill);
+            }
+        }
+    }
+}
+
+/// Scrolls the pixels of the `storage` in place pixel by pixel,
+/// shifting every row by `dx` and then every column by `dy`.
+pub(crate) fn scroll_lines<S: PixelStorage + ?Sized>(
+    storage: &mut S,
+    dx: i32,
+    dy: i32,
+    wrap: Wrap,
+) {
+    let size = storage.size();
+
+    if dx != 0 {
+        for y in 0..size.height as i32 {
+            let mut row = StorageLine::row(storage, y);
+            shift_line(&mut row, dx.into(), wrap);
+        }
+    }
+    if dy != 0 {
+        for x in 0..size.width as i32 {
+            let mut column = StorageLine::column(storage, x);
+            shift_line(&mut column, dy.into(), wrap);
+        }
+    }
+}
+
+/// A row or a column of the pixels of a storage.
+struct StorageLine<'a, S: ?Sized> {
+    storage: &'a mut S,
+    start: Point,
+    step: Point,
+    len: usize,
+}
+
+impl<'a, S: PixelStorage + ?Sized> StorageLine<'a, S> {
+    fn row(storage: &'a mut S, y: i32) -> Self {
+        let len = storage.size().width as usize;
+
+        Self {
+            storage,
+            start: Point::new(0, y),
+            step: Point::new(1, 0),
+            len,
+        }
+    }
+
+    fn column(storage: &'a mut S, x: i32) -> Self {
+        let len = storage.size().height as usize;
+
+        Self {
+            storage,
+            start: Point::new(x, 0),
+            step: Point::new(0, 1),
+            len,
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<S::Color> {
+        self.storage.get(self.point(index))
+    }
+
+    fn set(&mut self, index: usize, color: Option<S::Color>) {
+        let point = self.point(index);
+
+        self.storage.set(point, color);
+    }
+
+    fn point(&self, index: usize) -> Point {
+        self.start + self.step * index as i32
+    }
+}
+
+/// Same as [`shift`] but for the pixels of a [`StorageLine`],
+/// the uncovered pixels are transparent.
+fn shift_line<S: PixelStorage + ?Sized>(line: &mut StorageLine<'_, S>, by: i64, wrap: Wrap) {
+    let len = line.len;
+    if len == 0 || by == 0 {
+        return;
+    }
+
+    match wrap {
+        Wrap::Yes => {
+            // rotating to the end is reversing the whole line and then both parts of it
+            let amount = by.rem_euclid(len as i64) as usize;
+
+            reverse(line, 0, len);
+            reverse(line, 0, amount);
+            reverse(line, amount, len);
+        }
+        Wrap::No => {
+            let amount = by.unsigned_abs().min(len as u64) as usize;
+
+            if by > 0 {
+                for index in (amount..len).rev() {
+                    line.set(index, line.get(index - amount));
+                }
+                for index in 0..amount {
+                    line.set(index, None);
+                }
+            } else {
+                for index in 0..len - amount {
+                    line.set(index, line.get(index + amount));
+                }
+                for index in len - amount..len {
+                    line.set(index, None);
+                }
+            }
+        }
+    }
+}
+
+/// Reverses the pixels of the `line` from `start` to `end` (exclusive) in place.
+fn reverse<S: PixelStorage + ?Sized>(
+    line: &mut StorageLine<'_, S>,
+    mut start: usize,
+    mut end: usize,
+) {
+    while start + 1 < end {
+        end -= 1;
+
+        let color = line.get(start);
+        line.set(start, line.get(end));
+        line.set(end, color);
+
+        start += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+
+    use super::*;
+    use crate::{CBitplanes, RowMajor};
+
+    #[test]
+    fn test_shift() {
+        let mut items = [1, 2, 3, 4];
+        shift(&mut items, 1, Wrap::Yes, 0);
+        assert_eq!([4, 1, 2, 3], items);
+        shift(&mut items, -6, Wrap::Yes, 0);
+        assert_eq!([2, 3, 4, 1], items);
+
+        shift(&mut items, 1, Wrap::No, 0);
+        assert_eq!([0, 2, 3, 4], items);
+        shift(&mut items, -2, Wrap::No, 0);
+        assert_eq!([3, 4, 0, 0], items);
+        shift(&mut items, 10, Wrap::No, 0);
+        assert_eq!([0, 0, 0, 0], items);
+    }
+
+    #[test]
+    fn test_scroll_lines() {
+        let pixels = [
+            Pixel(Point::new(0, 0), BinaryColor::On),
+            Pixel(Point::new(2, 1), BinaryColor::Off),
+            Pixel(Point::new(1, 9), BinaryColor::On),
+        ];
+        let mut buffer = [None; 48];
+        let mut row_major = RowMajor::new(&mut buffer[..], Size::new(3, 16)).unwrap();
+        let mut bitplanes = CBitplanes::<3, 2>::new();
+        for Pixel(point, color) in pixels {
+            row_major.set(point, Some(color));
+            bitplanes.set(point, Some(color));
+        }
+
+        // the pixel by pixel scrolling matches the one of the slices
+        for (dx, dy, wrap) in [(1, -1, Wrap::Yes), (-4, 21, Wrap::Yes), (1, 2, Wrap::No)] {
+            row_major.scroll(dx, dy, wrap);
+            scroll_lines(&mut bitplanes, dx, dy, wrap);
+
+            for point in Rectangle::new(Point::zero(), Size::new(3, 16)).points() {
+                assert_eq!(row_major.get(point), bitplanes.get(point), "{point:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Canvases over a caller-provided slice of pixels.
+use embedded_graphics_core::{pixelcolor::PixelColor, prelude::*};
+
+use crate::{
+    canvas::{GenericCanvas, GenericCanvasAt},
+    storage::RowMajor,
+};
+#[cfg(feature = "alloc")]
+use crate::{Canvas, CanvasAt};
+#[cfg(feature = "alloc")]
+use embedded_graphics_core::primitives::Rectangle;
+
+/// Canvas on which you can draw which uses a caller-provided slice of pixels,
+/// e.g. a framebuffer placed in a specific RAM section, instead of allocating them.
+///
+/// The pixels are stored row by row in a [`RowMajor`] storage, as in [`Canvas`](crate::Canvas).
+///
+/// Draw on the [`SliceCanvas`] using origin of [`Point::zero()`].
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::SliceCanvas;
+/// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+///
+/// let mut framebuffer = [None; 64 * 32];
+/// let mut canvas = SliceCanvas::new(&mut framebuffer, Size::new(64, 32)).unwrap();
+///
+/// Pixel(Point::new(1, 1), Rgb565::RED).draw(&mut canvas)?;
+///
+/// let mut display = embedded_canvas::CCanvasAt::<_, 64, 32>::new(Point::zero());
+/// canvas.into_placed_at(Point::zero()).draw(&mut display)?;
+/// assert_eq!(Some(Rgb565::RED), display.get_pixel(Point::new(1, 1)));
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+pub type SliceCanvas<'a, C> = GenericCanvas<RowMajor<C, &'a mut [Option<C>]>>;
+
+impl<'a, C: PixelColor> SliceCanvas<'a, C> {
+    /// Create a new [`SliceCanvas`] with the given `pixels`, which are kept as they are.
+    ///
+    /// Only the first width * height pixels of the slice are used.
+    ///
+    /// Returns [`None`] if the slice has less than width * height pixels.
+    pub fn new(pixels: &'a mut [Option<C>], canvas: Size) -> Option<Self> {
+        RowMajor::new(pixels, canvas).map(GenericCanvas::from_storage)
+    }
+
+    /// Create a new cropped [`Canvas`].
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the [`SliceCanvas`] itself.
+    ///
+    /// If the width or height of the [`Rectangle`] is `0`, this method will
+    /// return [`None`] (see [`Rectangle::bottom_right()`])
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn crop(&self, area: &Rectangle) -> Option<Canvas<C>> {
+        // returns None when width or height is `0`
+        area.bottom_right()?;
+
+        let mut new = Canvas::new(area.size);
+        self.crop_into(area, &mut new).ok().map(|_| new)
+    }
+}
+
+/// Canvas which uses a caller-provided slice of pixels and
+/// is drawable at the provided [`Point`] (location) on the display.
+pub type SliceCanvasAt<'a, C> = GenericCanvasAt<RowMajor<C, &'a mut [Option<C>]>>;
+
+impl<'a, C: PixelColor> SliceCanvasAt<'a, C> {
+    /// Create a new [`SliceCanvasAt`] with the given `pixels`, which are kept as they are.
+    ///
+    /// Only the first width * height pixels of the slice are used.
+    ///
+    /// Returns [`None`] if the slice has less than width * height pixels.
+    pub fn new(top_left: Point, pixels: &'a mut [Option<C>], canvas: Size) -> Option<Self> {
+        SliceCanvas::new(pixels, canvas).map(|canvas| canvas.into_placed_at(top_left))
+    }
+
+    /// Create a new cropped [`CanvasAt`].
+    ///
+    /// This method takes into account the top left [`Point`] of the `area`
+    /// you'd like to crop relative to the **display**.
+    ///
+    /// If the width or height of the [`Rectangle`] is `0`, this method will
+    /// return [`None`] (see [`Rectangle::bottom_right()`])
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn crop(&self, area: &Rectangle) -> Option<CanvasAt<C>> {
+        // returns None when width or height is `0`
+        area.bottom_right()?;
+
+        let mut new = CanvasAt::new(area.top_left, area.size);
+        self.crop_into(area, &mut new).ok().map(|_| new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::{
+        pixelcolor::{Gray8, GrayColor},
+        primitives::Rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_slice_canvas() {
+        let mut pixels = [None; 10];
+        assert!(SliceCanvas::<Gray8>::new(&mut pixels, Size::new(4, 3)).is_none());
+
+        let mut canvas = SliceCanvas::new(&mut pixels, Size::new(3, 3)).unwrap();
+        assert_eq!(9, canvas.storage().pixels().len());
+
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(5, 5)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+
+        let mut canvas = canvas.into_placed_at(Point::new(10, 0));
+        let mut clipped = canvas.clipped_stack::<1>();
+        clipped
+            .push(&Rectangle::new(Point::new(10, 0), Size::new(1, 3)))
+            .unwrap();
+        clipped.clear(Gray8::BLACK).unwrap();
+        assert_eq!(Some(Gray8::BLACK), canvas.get_pixel(Point::new(10, 2)));
+        assert_eq!(Some(Gray8::WHITE), canvas.get_pixel(Point::new(11, 1)));
+        assert_eq!(None, canvas.get_pixel(Point::new(11, 0)));
+
+        let mut display = [None; 9];
+        let mut display =
+            SliceCanvasAt::new(Point::new(10, 0), &mut display, Size::new(3, 3)).unwrap();
+        canvas.draw(&mut display).unwrap();
+        assert_eq!(7, display.storage().pixels().iter().flatten().count());
+
+        // only the width * height pixels are cleared
+        canvas.clear(Gray8::WHITE).unwrap();
+
+        // the pixels are written in the slice
+        assert_eq!(Some(Gray8::WHITE), pixels[0]);
+        assert_eq!(None, pixels[9]);
+    }
+}
@@ -0,0 +1,339 @@
+//! Pluggable pixel storage for the generic canvases.
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+use core::marker::PhantomData;
+
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+use crate::scroll::{self, shift, Wrap};
+
+/// The storage of the pixels of a [`GenericCanvas`] and [`GenericCanvasAt`].
+///
+/// The points passed to the methods are always inside of [`PixelStorage::size`],
+/// so implementations don't need to check them.
+///
+/// Every canvas of the crate is a [`GenericCanvas`] over one of the storages:
+/// [`RowMajor`] buffers (e.g. a boxed or a borrowed slice), the column-major arrays
+/// of [`CCanvas`](crate::CCanvas), [`Masked`], [`Bitplanes`] and [`CBitplanes`].
+/// Implement it for your own storage, e.g. a packed buffer or an external memory.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{GenericCanvas, PixelStorage};
+/// use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+///
+/// /// 8x8 pixels where only the `On` pixels are drawn.
+/// struct Packed([u8; 8]);
+///
+/// impl PixelStorage for Packed {
+///     type Color = BinaryColor;
+///
+///     fn size(&self) -> Size {
+///         Size::new(8, 8)
+///     }
+///
+///     fn get(&self, point: Point) -> Option<BinaryColor> {
+///         (self.0[point.y as usize] & (1 << point.x) != 0).then_some(BinaryColor::On)
+///     }
+///
+///     fn set(&mut self, point: Point, color: Option<BinaryColor>) {
+///         let row = &mut self.0[point.y as usize];
+///         match color {
+///             Some(BinaryColor::On) => *row |= 1 << point.x,
+///             _ => *row &= !(1 << point.x),
+///         }
+///     }
+/// }
+///
+/// let mut canvas = GenericCanvas::from_storage(Packed([0; 8]));
+/// Pixel(Point::new(2, 1), BinaryColor::On).draw(&mut canvas)?;
+/// assert_eq!(0b100, canvas.storage().0[1]);
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+///
+/// [`GenericCanvas`]: crate::GenericCanvas
+/// [`GenericCanvasAt`]: crate::GenericCanvasAt
+/// [`Masked`]: crate::Masked
+/// [`Bitplanes`]: crate::Bitplanes
+/// [`CBitplanes`]: crate::CBitplanes
+pub trait PixelStorage {
+    /// The color of the pixels.
+    type Color: PixelColor;
+
+    /// Returns the size of the stored pixels.
+    fn size(&self) -> Size;
+
+    /// Returns the color of the pixel at a given [`Point`]
+    /// or [`None`] if the pixel is transparent.
+    fn get(&self, point: Point) -> Option<Self::Color>;
+
+    /// Sets the color of the pixel at a given [`Point`],
+    /// [`None`] makes the pixel transparent.
+    fn set(&mut self, point: Point, color: Option<Self::Color>);
+
+    /// Sets the color of all the pixels in the `area`.
+    ///
+    /// The default implementation sets the pixels one by one.
+    fn fill(&mut self, area: &Rectangle, color: Option<Self::Color>) {
+        for point in area.points() {
+            self.set(point, color);
+        }
+    }
+
+    /// Sets the `colors` of the pixels of a row from the `start` [`Point`] to the right,
+    /// the `colors` never go past the end of the row.
+    ///
+    /// The default implementation sets the pixels one by one.
+    fn set_row<I>(&mut self, start: Point, colors: I)
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        for (x, color) in (start.x..).zip(colors) {
+            self.set(Point::new(x, start.y), Some(color));
+        }
+    }
+
+    /// Scrolls the pixels in place by `dx` horizontally and `dy` vertically,
+    /// positive values scroll to the right and down (see [`GenericCanvas::scroll`](crate::GenericCanvas::scroll)).
+    ///
+    /// The default implementation shifts the rows and the columns pixel by pixel,
+    /// override it to copy whole rows or columns instead.
+    fn scroll(&mut self, dx: i32, dy: i32, wrap: Wrap) {
+        scroll::scroll_lines(self, dx, dy, wrap);
+    }
+}
+
+/// A [`PixelStorage`] which can be created with any size.
+///
+/// The canvases over such a storage get the methods returning new canvases,
+/// e.g. cropping, rotating by 90 degrees, resizing and scaling.
+pub trait NewPixelStorage: PixelStorage + Sized {
+    /// Create a new storage of the given `size` with all the pixels set to `color`.
+    ///
+    /// # Panics
+    ///
+    /// May panic when the pixels of the `size` don't fit in memory.
+    fn with_size(size: Size, color: Option<Self::Color>) -> Self;
+}
+
+/// A [`PixelStorage`] which stores the pixels row by row in a buffer,
+/// e.g. a `Box<[Option<C>]>`, a `&mut [Option<C>]` or an array.
+#[derive(Debug, Clone)]
+pub struct RowMajor<C, B> {
+    size: Size,
+    buffer: B,
+    color: PhantomData<C>,
+}
+
+impl<C, B> RowMajor<C, B>
+where
+    C: PixelColor,
+    B: AsRef<[Option<C>]> + AsMut<[Option<C>]>,
+{
+    /// Create a new [`RowMajor`] storage of the given `size` using the `buffer`.
+    ///
+    /// Returns [`None`] if the buffer has less than width * height pixels.
+    pub fn new(buffer: B, size: Size) -> Option<Self> {
+        let pixel_count = (size.width as usize).checked_mul(size.height as usize)?;
+
+        (buffer.as_ref().len() >= pixel_count).then_some(Self {
+            size,
+            buffer,
+            color: PhantomData,
+        })
+    }
+
+    /// Returns the buffer of the [`RowMajor`] storage.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Returns the width * height pixels of the [`RowMajor`] storage, row by row.
+    pub fn pixels(&self) -> &[Option<C>] {
+        &self.buffer.as_ref()[..self.pixel_count()]
+    }
+
+    /// Returns the width * height pixels of the [`RowMajor`] storage, row by row,
+    /// for modifying them directly.
+    pub fn pixels_mut(&mut self) -> &mut [Option<C>] {
+        let pixel_count = self.pixel_count();
+
+        &mut self.buffer.as_mut()[..pixel_count]
+    }
+
+    fn pixel_count(&self) -> usize {
+        self.size.width as usize * self.size.height as usize
+    }
+
+    fn index(&self, point: Point) -> usize {
+        point.x as usize + point.y as usize * self.size.width as usize
+    }
+}
+
+impl<C, B> PixelStorage for RowMajor<C, B>
+where
+    C: PixelColor,
+    B: AsRef<[Option<C>]> + AsMut<[Option<C>]>,
+{
+    type Color = C;
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn get(&self, point: Point) -> Option<C> {
+        self.buffer.as_ref()[self.index(point)]
+    }
+
+    fn set(&mut self, point: Point, color: Option<C>) {
+        let index = self.index(point);
+        self.buffer.as_mut()[index] = color;
+    }
+
+    fn fill(&mut self, area: &Rectangle, color: Option<C>) {
+        let width = area.size.width as usize;
+
+        for row in area.rows() {
+            let index = self.index(Point::new(area.top_left.x, row));
+            self.buffer.as_mut()[index..index + width].fill(color);
+        }
+    }
+
+    fn set_row<I>(&mut self, start: Point, colors: I)
+    where
+        I: IntoIterator<Item = C>,
+    {
+        let index = self.index(start);
+
+        for (pixel, color) in self.buffer.as_mut()[index..].iter_mut().zip(colors) {
+            *pixel = Some(color);
+        }
+    }
+    fn scroll(&mut self, dx: i32, dy: i32, wrap: Wrap) {
+        let width = self.size.width as usize;
+        // `chunks_exact_mut` panics for a chunk size of `0`
+        if width == 0 {
+            return;
+        }
+
+        let pixels = self.pixels_mut();
+        // the pixels are stored row by row, so scrolling vertically shifts whole rows
+        shift(pixels, i64::from(dy) * width as i64, wrap, None);
+        for row in pixels.chunks_exact_mut(width) {
+            shift(row, dx.into(), wrap, None);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: PixelColor> NewPixelStorage for RowMajor<C, Box<[Option<C>]>> {
+    /// # Panics
+    ///
+    /// Panics when width * height > [`usize::MAX`].
+    fn with_size(size: Size, color: Option<C>) -> Self {
+        let pixel_count = size.width as usize * size.height as usize;
+
+        Self {
+            size,
+            buffer: vec![color; pixel_count].into_boxed_slice(),
+            color: PhantomData,
+        }
+    }
+}
+
+impl<C: PixelColor, const W: usize, const H: usize> PixelStorage for [[Option<C>; H]; W] {
+    type Color = C;
+
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+
+    fn get(&self, point: Point) -> Option<C> {
+        self[point.x as usize][point.y as usize]
+    }
+
+    fn set(&mut self, point: Point, color: Option<C>) {
+        self[point.x as usize][point.y as usize] = color;
+    }
+
+    fn fill(&mut self, area: &Rectangle, color: Option<C>) {
+        let (x, y) = (area.top_left.x as usize, area.top_left.y as usize);
+        let (width, height) = (area.size.width as usize, area.size.height as usize);
+
+        for x_row in self[x..x + width].iter_mut() {
+            x_row[y..y + height].fill(color);
+        }
+    }
+    fn scroll(&mut self, dx: i32, dy: i32, wrap: Wrap) {
+        // the pixels are stored column by column
+        shift(self, dx.into(), wrap, [None; H]);
+        for column in self.iter_mut() {
+            shift(column, dy.into(), wrap, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use super::*;
+    use crate::{GenericCanvas, GenericCanvasAt};
+
+    #[test]
+    fn test_row_major() {
+        let mut buffer = [None; 7];
+        assert!(RowMajor::<Gray8, _>::new(&mut buffer[..], Size::new(4, 2)).is_none());
+
+        let storage = RowMajor::new(&mut buffer[..], Size::new(3, 2)).unwrap();
+        let mut canvas = GenericCanvas::from_storage(storage);
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 0), Size::new(5, 5)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+
+        let mut cropped = GenericCanvas::from_storage([[None; 2]; 2]);
+        canvas
+            .crop_into(
+                &Rectangle::new(Point::new(0, 1), Size::new(2, 2)),
+                &mut cropped,
+            )
+            .unwrap();
+        assert_eq!([[None; 2], [Some(Gray8::WHITE), None]], *cropped.storage());
+
+        assert_eq!(
+            [None, Some(Gray8::WHITE), Some(Gray8::WHITE)],
+            canvas.into_storage().into_inner()[3..6]
+        );
+    }
+
+    #[test]
+    fn test_generic_canvas_at() {
+        let mut canvas = GenericCanvasAt::from_storage(Point::new(10, 10), [[None; 3]; 3]);
+        let mut clipped = canvas.clipped_stack::<1>();
+        clipped
+            .push(&Rectangle::new(Point::new(11, 11), Size::new(5, 5)))
+            .unwrap();
+
+        clipped
+            .fill_contiguous(
+                &Rectangle::new(Point::new(10, 10), Size::new(3, 3)),
+                (0..9).map(Gray8::new),
+            )
+            .unwrap();
+        assert_eq!(None, canvas.get_pixel(Point::new(10, 10)));
+        assert_eq!(Some(Gray8::new(4)), canvas.get_pixel(Point::new(11, 11)));
+        assert_eq!(Some(Gray8::new(8)), canvas.get_pixel(Point::new(12, 12)));
+
+        Pixel(Point::new(10, 12), Gray8::WHITE)
+            .draw(&mut canvas)
+            .unwrap();
+
+        let mut display = GenericCanvasAt::from_storage(Point::new(10, 10), [[None; 3]; 3]);
+        canvas.draw(&mut display).unwrap();
+        assert_eq!(5, display.storage().iter().flatten().flatten().count());
+    }
+}
@@ -0,0 +1,180 @@
+//! Mutable views into a region of a canvas.
+use embedded_graphics_core::{prelude::*, primitives::Rectangle};
+
+use crate::{canvas::storage_pixel, storage::PixelStorage, utils::fill_clipped_rows};
+
+/// A mutable view into an area of a [`GenericCanvas`](crate::GenericCanvas),
+/// e.g. a [`Canvas`](crate::Canvas), with its own origin at the top left corner of the area.
+///
+/// Drawing on the [`SubCanvasMut`] writes directly into the storage of the canvas
+/// and only changes the pixels inside of the area.
+///
+/// Created with [`GenericCanvas::sub_canvas_mut`](crate::GenericCanvas::sub_canvas_mut).
+#[derive(Debug)]
+pub struct SubCanvasMut<'a, S> {
+    /// The area of the [`SubCanvasMut`] in the canvas.
+    area: Rectangle,
+    /// The part of the area inside of the canvas.
+    bounds: Rectangle,
+    storage: &'a mut S,
+}
+
+impl<'a, S: PixelStorage> SubCanvasMut<'a, S> {
+    pub(crate) fn new(area: Rectangle, storage: &'a mut S) -> Self {
+        Self {
+            area,
+            bounds: Rectangle::new(Point::zero(), storage.size()).intersection(&area),
+            storage,
+        }
+    }
+
+    /// Returns the area of the [`SubCanvasMut`] in the canvas.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Returns the color of the pixel at a given [`Point`] of the [`SubCanvasMut`].
+    ///
+    /// Returns [`None`] if the [`Point`] is outside of the [`SubCanvasMut`].
+    pub fn get_pixel(&self, point: Point) -> Option<S::Color> {
+        let point = point + self.area.top_left;
+        if !self.area.contains(point) {
+            return None;
+        }
+
+        storage_pixel(self.storage, point)
+    }
+}
+
+impl<S> OriginDimensions for SubCanvasMut<'_, S> {
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+impl<S: PixelStorage> DrawTarget for SubCanvasMut<'_, S> {
+    type Color = S::Color;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels.into_iter() {
+            let point = point + self.area.top_left;
+            // the bounds are inside of the canvas
+            if self.bounds.contains(point) {
+                self.storage.set(point, Some(color));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+        let storage = &mut *self.storage;
+
+        fill_clipped_rows(&area, &self.bounds, colors, |row_start, row_colors| {
+            storage.set_row(row_start, row_colors);
+        });
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+        let filled = area.intersection(&self.bounds);
+
+        if !filled.is_zero_sized() {
+            self.storage.fill(&filled, Some(color));
+        }
+
+        Ok(())
+    }
+}
+
+/// A mutable view into an area of a [`CCanvas`](crate::CCanvas) with its own origin
+/// at the top left corner of the area (see [`SubCanvasMut`]).
+pub type CSubCanvasMut<'a, C, const W: usize, const H: usize> =
+    SubCanvasMut<'a, [[Option<C>; H]; W]>;
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use crate::CCanvas;
+
+    use super::*;
+
+    #[test]
+    fn test_c_sub_canvas_mut_negative_top_left() {
+        let mut canvas = CCanvas::<Gray8, 4, 4>::new();
+
+        // only the bottom right 2x2 pixels of the area are inside of the canvas
+        let mut sub_canvas =
+            canvas.sub_canvas_mut(&Rectangle::new(Point::new(-2, -1), Size::new(4, 3)));
+        assert_eq!(Size::new(4, 3), sub_canvas.size());
+
+        sub_canvas
+            .fill_contiguous(
+                &Rectangle::new(Point::zero(), Size::new(4, 3)),
+                (0..12).map(Gray8::new),
+            )
+            .unwrap();
+        // outside of the canvas
+        Pixel(Point::new(1, 0), Gray8::WHITE)
+            .draw(&mut sub_canvas)
+            .unwrap();
+        assert_eq!(None, sub_canvas.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Gray8::new(6)), sub_canvas.get_pixel(Point::new(2, 1)));
+
+        // the colors stay aligned with the points of the area
+        assert_eq!(Some(Gray8::new(6)), canvas.get_pixel(Point::new(0, 0)));
+        assert_eq!(Some(Gray8::new(7)), canvas.get_pixel(Point::new(1, 0)));
+        assert_eq!(Some(Gray8::new(10)), canvas.get_pixel(Point::new(0, 1)));
+        assert_eq!(Some(Gray8::new(11)), canvas.get_pixel(Point::new(1, 1)));
+        assert_eq!(None, canvas.get_pixel(Point::new(2, 0)));
+        assert_eq!(None, canvas.get_pixel(Point::new(0, 2)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sub_canvas_mut_outside_of_canvas() {
+        let mut canvas = crate::Canvas::<Gray8>::new(Size::new(4, 2));
+
+        // the area doesn't intersect the canvas
+        let mut sub_canvas =
+            canvas.sub_canvas_mut(&Rectangle::new(Point::new(-3, -3), Size::new(2, 2)));
+        sub_canvas.clear(Gray8::WHITE).unwrap();
+        sub_canvas
+            .fill_contiguous(
+                &Rectangle::new(Point::new(-1, -1), Size::new(4, 4)),
+                (0..16).map(Gray8::new),
+            )
+            .unwrap();
+        assert!(canvas.storage().pixels().iter().all(Option::is_none));
+
+        // the area sticks out on the right, the rows don't wrap into the next one
+        let mut sub_canvas =
+            canvas.sub_canvas_mut(&Rectangle::new(Point::new(2, 0), Size::new(4, 2)));
+        sub_canvas
+            .fill_contiguous(
+                &Rectangle::new(Point::zero(), Size::new(4, 2)),
+                (1..=8).map(Gray8::new),
+            )
+            .unwrap();
+        assert_eq!(
+            [None, None, Some(1), Some(2), None, None, Some(5), Some(6)],
+            canvas
+                .storage()
+                .pixels()
+                .iter()
+                .map(|color| color.map(|color| color.luma()))
+                .collect::<alloc::vec::Vec<_>>()[..]
+        );
+    }
+}
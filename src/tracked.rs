@@ -0,0 +1,250 @@
+//! Opt-in tracking of the area drawn on a canvas.
+use embedded_graphics_core::{image::GetPixel, prelude::*, primitives::Rectangle};
+
+use crate::utils::{draw_spans, extend_dirty};
+
+/// A [`DrawTarget`] adapter which tracks the area drawn on the target, so only
+/// the changed area is sent to the display with [`Tracked::draw_dirty`].
+///
+/// The dirty area is the bounding box of everything drawn through the [`Tracked`]
+/// target since it was created or since the last [`Tracked::draw_dirty`] or
+/// [`Tracked::reset_dirty`]. It uses the coordinates of the target,
+/// i.e. the **display** coordinates for the canvases placed on the display.
+///
+/// Drawing on the target directly, e.g. through [`Tracked::get_mut`], isn't tracked,
+/// use [`Tracked::mark_dirty`] for such changes.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_canvas::{CCanvasAt, Tracked};
+/// use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+///
+/// let mut canvas = Tracked::new(CCanvasAt::<_, 32, 32>::new(Point::new(10, 10)));
+/// canvas.fill_solid(
+///     &Rectangle::new(Point::new(12, 12), Size::new(4, 4)),
+///     Rgb565::RED,
+/// )?;
+/// assert_eq!(
+///     Some(Rectangle::new(Point::new(12, 12), Size::new(4, 4))),
+///     canvas.dirty_area()
+/// );
+///
+/// // only the 4x4 pixels are drawn on the display
+/// let mut display = CCanvasAt::<_, 64, 64>::new(Point::zero());
+/// canvas.draw_dirty(&mut display)?;
+/// assert_eq!(None, canvas.dirty_area());
+/// # Ok::<(), core::convert::Infallible>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tracked<T> {
+    target: T,
+    dirty: Option<Rectangle>,
+}
+
+impl<T> Tracked<T> {
+    /// Create a new [`Tracked`] target without a dirty area.
+    pub fn new(target: T) -> Self {
+        Self {
+            target,
+            dirty: None,
+        }
+    }
+
+    /// Returns the target.
+    pub fn get_ref(&self) -> &T {
+        &self.target
+    }
+
+    /// Returns the target for modifying it directly,
+    /// the changes are not tracked in the dirty area.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.target
+    }
+
+    /// Returns the target.
+    pub fn into_inner(self) -> T {
+        self.target
+    }
+
+    /// Returns the area drawn since the [`Tracked`] target was created or
+    /// since the last [`Tracked::draw_dirty`] or [`Tracked::reset_dirty`].
+    ///
+    /// Returns [`None`] if nothing has been drawn.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        self.dirty
+    }
+
+    /// Adds the `area` to the dirty area, e.g. after changing the pixels of the target directly.
+    pub fn mark_dirty(&mut self, area: &Rectangle) {
+        extend_dirty(&mut self.dirty, *area);
+    }
+
+    /// Resets the dirty area.
+    pub fn reset_dirty(&mut self) {
+        self.dirty = None;
+    }
+}
+
+impl<T: GetPixel> Tracked<T> {
+    /// Draws only the dirty area of the target on the display
+    /// and resets the dirty area.
+    ///
+    /// Only the drawn pixels of the target are drawn on the display,
+    /// the transparent ones are skipped.
+    /// If drawing on the display fails the dirty area is kept.
+    pub fn draw_dirty<D>(&mut self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T::Color>,
+    {
+        if let Some(dirty) = self.dirty {
+            draw_spans(&dirty, |point| self.target.pixel(point), display)?;
+        }
+
+        self.reset_dirty();
+
+        Ok(())
+    }
+}
+
+impl<T: Dimensions> Dimensions for Tracked<T> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<T: GetPixel> GetPixel for Tracked<T> {
+    type Color = T::Color;
+
+    fn pixel(&self, point: Point) -> Option<Self::Color> {
+        self.target.pixel(point)
+    }
+}
+
+impl<T: DrawTarget> DrawTarget for Tracked<T> {
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.target.bounding_box();
+        // the top left and bottom right corners of the drawn pixels
+        let mut corners: Option<(Point, Point)> = None;
+
+        let pixels = pixels.into_iter().inspect(|Pixel(point, _color)| {
+            if !bounds.contains(*point) {
+                return;
+            }
+
+            corners = Some(match corners {
+                Some((top_left, bottom_right)) => (
+                    top_left.component_min(*point),
+                    bottom_right.component_max(*point),
+                ),
+                None => (*point, *point),
+            });
+        });
+        let result = self.target.draw_iter(pixels);
+
+        if let Some((top_left, bottom_right)) = corners {
+            extend_dirty(
+                &mut self.dirty,
+                Rectangle::with_corners(top_left, bottom_right),
+            );
+        }
+
+        result
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        extend_dirty(
+            &mut self.dirty,
+            area.intersection(&self.target.bounding_box()),
+        );
+
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        extend_dirty(
+            &mut self.dirty,
+            area.intersection(&self.target.bounding_box()),
+        );
+
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        extend_dirty(&mut self.dirty, self.target.bounding_box());
+
+        self.target.clear(color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+
+    use super::*;
+    use crate::CCanvasAt;
+
+    #[test]
+    fn test_tracked_draw_iter() {
+        let mut canvas = Tracked::new(CCanvasAt::<Gray8, 4, 4>::new(Point::new(2, 2)));
+
+        // the pixels outside of the canvas aren't dirty
+        canvas
+            .draw_iter([
+                Pixel(Point::new(5, 2), Gray8::WHITE),
+                Pixel(Point::new(3, 4), Gray8::WHITE),
+                Pixel(Point::new(0, 0), Gray8::WHITE),
+                Pixel(Point::new(9, 9), Gray8::WHITE),
+            ])
+            .unwrap();
+        assert_eq!(
+            Some(Rectangle::new(Point::new(3, 2), Size::new(3, 3))),
+            canvas.dirty_area()
+        );
+
+        // changes to the canvas itself aren't tracked
+        canvas.reset_dirty();
+        canvas.get_mut().clear(Gray8::BLACK).unwrap();
+        assert_eq!(None, canvas.dirty_area());
+        canvas.mark_dirty(&Rectangle::new(Point::new(2, 2), Size::new(1, 1)));
+        assert_eq!(
+            Some(Rectangle::new(Point::new(2, 2), Size::new(1, 1))),
+            canvas.dirty_area()
+        );
+    }
+
+    #[test]
+    fn test_tracked_draw_dirty() {
+        let mut canvas = Tracked::new(CCanvasAt::<Gray8, 4, 4>::new(Point::new(2, 2)));
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 0), Size::new(4, 4)),
+                Gray8::WHITE,
+            )
+            .unwrap();
+        assert_eq!(
+            Some(Rectangle::new(Point::new(4, 2), Size::new(2, 2))),
+            canvas.dirty_area()
+        );
+
+        let mut display = CCanvasAt::<Gray8, 8, 8>::with_default_color(Point::zero(), Gray8::BLACK);
+        // drawn before tracking the canvas
+        Pixel(Point::new(2, 2), Gray8::WHITE)
+            .draw(canvas.get_mut())
+            .unwrap();
+        canvas.draw_dirty(&mut display).unwrap();
+
+        assert_eq!(None, canvas.dirty_area());
+        assert_eq!(Some(Gray8::WHITE), display.get_pixel(Point::new(5, 3)));
+        assert_eq!(Some(Gray8::BLACK), display.get_pixel(Point::new(2, 2)));
+    }
+}
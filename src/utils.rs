@@ -1,4 +1,12 @@
-use embedded_graphics_core::geometry::Size;
+use core::iter::Take;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::PixelColor,
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
 
 /// Returns the center offset.
 ///
@@ -7,3 +15,304 @@ use embedded_graphics_core::geometry::Size;
 pub fn center_offset(size: Size) -> Size {
     size.saturating_sub(Size::new_equal(1)) / 2
 }
+
+/// Returns the number of bytes needed for a bit mask of `bits` bits.
+#[cfg(feature = "alloc")]
+pub(crate) fn mask_len(bits: usize) -> usize {
+    bits / 8 + usize::from(bits % 8 != 0)
+}
+
+/// Returns whether the bit at `index` is set in the bit mask.
+#[cfg(feature = "alloc")]
+pub(crate) fn get_bit(mask: &[u8], index: usize) -> bool {
+    mask.get(index / 8)
+        .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+}
+
+/// Sets or clears the bit at `index` in the bit mask.
+#[cfg(feature = "alloc")]
+pub(crate) fn set_bit(mask: &mut [u8], index: usize, value: bool) {
+    if let Some(byte) = mask.get_mut(index / 8) {
+        if value {
+            *byte |= 1 << (index % 8);
+        } else {
+            *byte &= !(1 << (index % 8));
+        }
+    }
+}
+
+/// Fills the part of the `area` inside of the `bounds` with `colors` given in
+/// row-major order for the whole `area`.
+///
+/// The area is clipped only once and `fill_row` is called for each clipped row
+/// with the first [`Point`] of the row and the colors of the row.
+pub(crate) fn fill_clipped_rows<C, I, F>(
+    area: &Rectangle,
+    bounds: &Rectangle,
+    colors: I,
+    mut fill_row: F,
+) where
+    I: IntoIterator<Item = C>,
+    F: FnMut(Point, &mut Take<&mut I::IntoIter>),
+{
+    let clipped = area.intersection(bounds);
+
+    if clipped.is_zero_sized() {
+        return;
+    }
+
+    let area_width = area.size.width as usize;
+    let width = clipped.size.width as usize;
+    let skip_left = (clipped.top_left.x - area.top_left.x) as usize;
+    // the colors outside of the clipped area between 2 rows
+    let skip_between = area_width - width;
+
+    let mut colors = colors.into_iter();
+
+    // skip the rows above the clipped area and the left part of the first row
+    let skip = (clipped.top_left.y - area.top_left.y) as usize * area_width + skip_left;
+    if skip > 0 && colors.nth(skip - 1).is_none() {
+        return;
+    }
+
+    for row in clipped.rows() {
+        let mut row_colors = colors.by_ref().take(width);
+        fill_row(Point::new(clipped.top_left.x, row), &mut row_colors);
+        // make sure the whole row is consumed
+        row_colors.for_each(drop);
+
+        if skip_between > 0 && colors.nth(skip_between - 1).is_none() {
+            return;
+        }
+    }
+}
+
+/// Runs of drawn pixels narrower than this are buffered and drawn with
+/// [`DrawTarget::draw_iter`] instead of filling a span for each of them.
+const MIN_SPAN_WIDTH: usize = 4;
+
+/// The number of pixels of the narrow runs buffered before drawing them.
+const PIXEL_BUFFER: usize = 64;
+
+/// Draws the pixels of the `area` using [`DrawTarget::fill_contiguous`] for
+/// each rectangular span of drawn pixels, skipping the transparent gaps.
+///
+/// Display drivers are a lot faster when filling rectangular spans of pixels,
+/// so consecutive rows which are fully drawn are merged in a single span
+/// and only the narrow runs of pixels are drawn one by one.
+///
+/// Every row is scanned once, `get_pixel` is called again only for the
+/// colors of the filled spans.
+pub(crate) fn draw_spans<C, D, F>(
+    area: &Rectangle,
+    get_pixel: F,
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    C: PixelColor,
+    D: DrawTarget<Color = C>,
+    F: Fn(Point) -> Option<C>,
+{
+    let start_x = area.top_left.x;
+    let end_x = start_x + area.size.width as i32;
+
+    let fill_span = |target: &mut D, span: Rectangle| {
+        target.fill_contiguous(&span, span.points().filter_map(&get_pixel))
+    };
+
+    let mut buffer = [None; PIXEL_BUFFER];
+    let mut buffered = 0;
+
+    // the first row of the consecutive fully drawn rows
+    let mut full_rows_start = None;
+
+    for y in area.rows() {
+        let mut row_full = false;
+        let mut x = start_x;
+
+        while x < end_x {
+            // the colors of the run, as long as it's narrow
+            let mut run = [None; MIN_SPAN_WIDTH];
+            let run_start = x;
+
+            while x < end_x {
+                let Some(color) = get_pixel(Point::new(x, y)) else {
+                    break;
+                };
+
+                if let Some(slot) = run.get_mut((x - run_start) as usize) {
+                    *slot = Some(color);
+                }
+                x += 1;
+            }
+
+            let width = (x - run_start) as usize;
+            if width == area.size.width as usize {
+                row_full = true;
+            } else if width >= MIN_SPAN_WIDTH {
+                let size = Size::new(width as u32, 1);
+                fill_span(target, Rectangle::new(Point::new(run_start, y), size))?;
+            } else {
+                let pixels = (run_start..).zip(run.into_iter().flatten());
+
+                for (x, color) in pixels {
+                    if buffered == PIXEL_BUFFER {
+                        target.draw_iter(buffer.iter().flatten().copied())?;
+                        buffered = 0;
+                    }
+
+                    buffer[buffered] = Some(Pixel(Point::new(x, y), color));
+                    buffered += 1;
+                }
+            }
+
+            // skip the transparent pixel which ended the run
+            x += 1;
+        }
+
+        if row_full {
+            full_rows_start.get_or_insert(y);
+        } else if let Some(start_y) = full_rows_start.take() {
+            let size = Size::new(area.size.width, (y - start_y) as u32);
+            fill_span(target, Rectangle::new(Point::new(start_x, start_y), size))?;
+        }
+    }
+
+    if let Some(start_y) = full_rows_start {
+        let end_y = area.top_left.y + area.size.height as i32;
+        let size = Size::new(area.size.width, (end_y - start_y) as u32);
+        fill_span(target, Rectangle::new(Point::new(start_x, start_y), size))?;
+    }
+
+    if buffered > 0 {
+        target.draw_iter(buffer[..buffered].iter().flatten().copied())?;
+    }
+
+    Ok(())
+}
+
+/// Returns [`None`] if the `area` has a width or height of `0`.
+pub(crate) fn non_empty(area: Rectangle) -> Option<Rectangle> {
+    if area.is_zero_sized() {
+        None
+    } else {
+        Some(area)
+    }
+}
+
+/// Extends the `dirty` area to the bounding box of the `dirty` area and the drawn `area`.
+pub(crate) fn extend_dirty(dirty: &mut Option<Rectangle>, area: Rectangle) {
+    let area_bottom_right = match area.bottom_right() {
+        Some(bottom_right) => bottom_right,
+        // nothing has been drawn
+        None => return,
+    };
+
+    *dirty = match *dirty {
+        Some(current) => {
+            let current_bottom_right = current.bottom_right().expect("Never zero sized");
+
+            Some(Rectangle::with_corners(
+                current.top_left.component_min(area.top_left),
+                current_bottom_right.component_max(area_bottom_right),
+            ))
+        }
+        None => Some(area),
+    };
+}
+
+/// Draws the pixels of the `area` of an image at the origin of the target,
+/// i.e. the top left corner of the `area` is drawn at [`Point::zero()`].
+pub(crate) fn draw_sub_image<C, D, F>(
+    area: &Rectangle,
+    get_pixel: F,
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    C: PixelColor,
+    D: DrawTarget<Color = C>,
+    F: Fn(Point) -> Option<C>,
+{
+    // pixels outside of the image are transparent
+    draw_spans(
+        &Rectangle::new(Point::zero(), area.size),
+        |point| get_pixel(point + area.top_left),
+        target,
+    )
+}
+
+/// Clockwise rotation of a canvas by a multiple of 90 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rotation {
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// Returns the size of the rotated canvas.
+    pub(crate) fn size(self, size: Size) -> Size {
+        match self {
+            Rotation::Deg180 => size,
+            Rotation::Deg90 | Rotation::Deg270 => Size::new(size.height, size.width),
+        }
+    }
+
+    /// Returns the position of the `point` in the rotated canvas,
+    /// where `size` is the size of the canvas before the rotation.
+    pub(crate) fn point(self, size: Size, point: Point) -> Point {
+        let (width, height) = (size.width as i32, size.height as i32);
+
+        match self {
+            Rotation::Deg90 => Point::new(height - 1 - point.y, point.x),
+            Rotation::Deg180 => Point::new(width - 1 - point.x, height - 1 - point.y),
+            Rotation::Deg270 => Point::new(point.y, width - 1 - point.x),
+        }
+    }
+}
+
+/// Mirroring of a canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Flip {
+    /// Mirrors the left and right side.
+    Horizontal,
+    /// Mirrors the top and bottom side.
+    Vertical,
+}
+
+impl Flip {
+    /// Returns the position of the `point` in the mirrored canvas with the given `size`.
+    pub(crate) fn point(self, size: Size, point: Point) -> Point {
+        let (width, height) = (size.width as i32, size.height as i32);
+
+        match self {
+            Flip::Horizontal => Point::new(width - 1 - point.x, point.y),
+            Flip::Vertical => Point::new(point.x, height - 1 - point.y),
+        }
+    }
+}
+
+/// Returns the area of the source canvas with `source_size` to copy and the top left
+/// corner where it's copied in the destination canvas with `destination_size`.
+///
+/// The `source_area` is copied to `destination` and both are clipped to the canvases.
+/// Returns [`None`] if nothing is copied.
+pub(crate) fn blit_area(
+    source_size: Size,
+    source_area: &Rectangle,
+    destination_size: Size,
+    destination: Point,
+) -> Option<(Rectangle, Point)> {
+    let offset = destination - source_area.top_left;
+
+    let source_area = source_area.intersection(&Rectangle::new(Point::zero(), source_size));
+    let destination_area = Rectangle::new(source_area.top_left + offset, source_area.size)
+        .intersection(&Rectangle::new(Point::zero(), destination_size));
+
+    non_empty(destination_area).map(|destination_area| {
+        (
+            Rectangle::new(destination_area.top_left - offset, destination_area.size),
+            destination_area.top_left,
+        )
+    })
+}